@@ -0,0 +1,484 @@
+use crate::{Correctness, Guess};
+use std::fmt;
+
+/// Everything a history of guesses says about the answer, compiled so that words can be checked
+/// against all of it at once.
+///
+/// For each position, this keeps the letters that may still be there, and for each letter, the
+/// fewest and the most times it may appear. A word fits the constraints exactly when
+/// [`Guess::matches`] accepts it for every guess in the history. Tiles of unknown color are the
+/// one exception: only what the known tiles say is used, so a few words may fit the constraints
+/// that `Guess::matches` would reject.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraints {
+    /// The letters each position may have, with bit `n` set for the `n`th letter of the alphabet.
+    allowed: [u32; 5],
+    /// The letters known to be at each position.
+    fixed: [Option<u8>; 5],
+    /// The fewest times each letter must appear.
+    min: [u8; 26],
+    /// The most times each letter may appear.
+    max: [u8; 26],
+    /// The letters whose count is constrained by `min` or `max`.
+    counted: u32,
+    /// True if some guess got colors that no word could give it.
+    impossible: bool,
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Self {
+            allowed: [(1 << 26) - 1; 5],
+            fixed: [None; 5],
+            min: [0; 26],
+            max: [5; 26],
+            counted: 0,
+            impossible: false,
+        }
+    }
+}
+
+impl Constraints {
+    pub fn new(history: &[Guess]) -> Self {
+        let mut this = Self::default();
+        for guess in history {
+            this.add(guess);
+        }
+        this
+    }
+
+    /// Narrows down the constraints with one more guess.
+    ///
+    /// The guessed word must be five lowercase ASCII letters, like the words in
+    /// [`DICTIONARY`](crate::DICTIONARY).
+    pub fn add(&mut self, guess: &Guess) {
+        assert_eq!(guess.word.len(), 5);
+        assert!(
+            guess.word.bytes().all(|b| b.is_ascii_lowercase()),
+            "guess '{}' must only have lowercase ASCII letters",
+            guess.word
+        );
+        let letters: Vec<_> = guess.word.bytes().map(|b| b - b'a').collect();
+        for (i, (&letter, &c)) in letters.iter().zip(&guess.mask).enumerate() {
+            match c {
+                Correctness::Correct => {
+                    self.allowed[i] &= 1 << letter;
+                    self.fixed[i] = Some(letter);
+                }
+                Correctness::Misplaced | Correctness::Wrong => self.allowed[i] &= !(1 << letter),
+                Correctness::Unknown => {}
+            }
+        }
+
+        for &letter in &letters {
+            let mut found = 0;
+            let mut unknown = 0;
+            let mut wrong = false;
+            for (&l, &c) in letters.iter().zip(&guess.mask) {
+                if l != letter {
+                    continue;
+                }
+                match c {
+                    Correctness::Correct => found += 1,
+                    Correctness::Misplaced => {
+                        // Copies of a letter are yellow from left to right, so a gray copy means
+                        // there were no more to make this one yellow.
+                        if wrong {
+                            self.impossible = true;
+                        }
+                        found += 1;
+                    }
+                    Correctness::Wrong => wrong = true,
+                    Correctness::Unknown => unknown += 1,
+                }
+            }
+            let l = usize::from(letter);
+            self.min[l] = self.min[l].max(found);
+            if wrong {
+                // A gray copy means that the green and yellow ones are all there are.
+                self.max[l] = self.max[l].min(found + unknown);
+            }
+            if self.min[l] > 0 || self.max[l] < 5 {
+                self.counted |= 1 << letter;
+            }
+        }
+    }
+
+    /// Returns true if `word` could be the answer.
+    pub fn allows(&self, word: &str) -> bool {
+        let bytes = word.as_bytes();
+        if self.impossible || bytes.len() != 5 {
+            return false;
+        }
+        let mut counts = [0u8; 26];
+        for (&b, allowed) in bytes.iter().zip(self.allowed) {
+            if !b.is_ascii_lowercase() {
+                return false;
+            }
+            let letter = b - b'a';
+            if allowed & (1 << letter) == 0 {
+                return false;
+            }
+            counts[usize::from(letter)] += 1;
+        }
+        let mut counted = self.counted;
+        while counted != 0 {
+            let l = counted.trailing_zeros() as usize;
+            counted &= counted - 1;
+            if counts[l] < self.min[l] || counts[l] > self.max[l] {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns a regular expression that, along with [`letter_counts`](Self::letter_counts),
+    /// matches exactly the allowed words.
+    ///
+    /// The expression matches a whole line of five lowercase letters, and only uses syntax that
+    /// `grep -E` understands.
+    pub fn regex(&self) -> String {
+        let mut regex = String::from("^");
+        for allowed in self.positions() {
+            regex.push_str(&letter_class(allowed));
+        }
+        regex.push('$');
+        regex
+    }
+
+    /// Returns the letter counts that allowed words must have beyond what [`regex`](Self::regex)
+    /// already requires.
+    pub fn letter_counts(&self) -> Vec<LetterCount> {
+        let positions = self.positions();
+        let mut counts = Vec::new();
+        for l in 0..26 {
+            let bit = 1 << l;
+            let fixed = positions.iter().filter(|&&a| a == bit).count();
+            let possible = positions.iter().filter(|&&a| a & bit != 0).count();
+            let min = usize::from(self.min[l]);
+            let max = usize::from(self.max[l]);
+            let count = LetterCount {
+                letter: char::from(b'a' + l as u8),
+                min: if min > fixed { min } else { 0 },
+                max: if max < possible { max } else { 5 },
+            };
+            if count.min > 0 || count.max < 5 {
+                counts.push(count);
+            }
+        }
+        counts
+    }
+
+    /// The letters each position may have, taking letters that can't appear at all into account.
+    fn positions(&self) -> [u32; 5] {
+        if self.impossible {
+            return [0; 5];
+        }
+        let absent = (0..26)
+            .filter(|&l| self.max[l] == 0)
+            .fold(0, |absent, l| absent | 1 << l);
+        self.allowed.map(|allowed| allowed & !absent)
+    }
+
+    /// Returns true if some guess got colors that it can't get for any word.
+    ///
+    /// Other contradictions, like two guesses that disagree, only show up as no word being
+    /// allowed.
+    pub fn is_impossible(&self) -> bool {
+        self.impossible
+    }
+
+    /// Checks that `word` uses all the hints revealed so far, as the game requires in hard mode.
+    ///
+    /// Like the game, this only checks that green letters stay where they are and that the guess
+    /// has at least as many of each letter as were found. It's fine to guess a letter that's known
+    /// not to be in the answer, or to put a yellow letter where it's known not to be.
+    pub fn check_hard_mode(&self, word: &str) -> Result<(), HardModeError> {
+        let bytes = word.as_bytes();
+        for (i, fixed) in self.fixed.into_iter().enumerate() {
+            if let Some(letter) = fixed {
+                if bytes.get(i) != Some(&(b'a' + letter)) {
+                    return Err(HardModeError::MissingLetterAt {
+                        position: i,
+                        letter: char::from(b'a' + letter),
+                    });
+                }
+            }
+        }
+        for (letter, &min) in (b'a'..=b'z').zip(&self.min) {
+            let count = bytes.iter().filter(|&&b| b == letter).count();
+            if count < usize::from(min) {
+                return Err(HardModeError::MissingLetter(char::from(letter)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the letters set in `letters` as a regular expression that matches any one of them.
+fn letter_class(letters: u32) -> String {
+    match letters.count_ones() {
+        // Nothing matches a character that isn't a letter, since words only have letters.
+        0 => return String::from("[^a-z]"),
+        1 => return char::from(b'a' + letters.trailing_zeros() as u8).to_string(),
+        26 => return String::from("[a-z]"),
+        _ => {}
+    }
+    let mut class = String::from("[");
+    let mut l = 0;
+    while l < 26 {
+        if letters & (1 << l) == 0 {
+            l += 1;
+            continue;
+        }
+        let start = l;
+        while l < 26 && letters & (1 << l) != 0 {
+            l += 1;
+        }
+        let letter = |l: u8| char::from(b'a' + l);
+        class.push(letter(start));
+        match l - start {
+            1 => {}
+            2 => class.push(letter(start + 1)),
+            _ => {
+                class.push('-');
+                class.push(letter(l - 1));
+            }
+        }
+    }
+    class.push(']');
+    class
+}
+
+/// How many times a letter has to appear in a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LetterCount {
+    pub letter: char,
+    /// The fewest times the letter must appear, which is 0 if there's no such limit.
+    pub min: usize,
+    /// The most times the letter may appear, which is 5 if there's no such limit.
+    pub max: usize,
+}
+
+impl LetterCount {
+    /// Returns true if `word` has the letter as many times as it should.
+    pub fn allows(&self, word: &str) -> bool {
+        let n = word.chars().filter(|&c| c == self.letter).count();
+        (self.min..=self.max).contains(&n)
+    }
+}
+
+/// Why a guess isn't allowed in hard mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HardModeError {
+    /// The guess doesn't have a green letter in the (0-based) position it was found at.
+    MissingLetterAt { position: usize, letter: char },
+    /// The guess has fewer of the letter than have been found to be in the answer.
+    MissingLetter(char),
+}
+
+impl fmt::Display for HardModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingLetterAt { position, letter } => {
+                let nth = ["1st", "2nd", "3rd", "4th", "5th"][*position];
+                write!(f, "{} letter must be {}", nth, letter.to_ascii_uppercase())
+            }
+            Self::MissingLetter(letter) => {
+                write!(f, "guess must contain {}", letter.to_ascii_uppercase())
+            }
+        }
+    }
+}
+
+impl std::error::Error for HardModeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Constraints, HardModeError, LetterCount};
+    use crate::{Correctness, Guess, DICTIONARY};
+    use std::borrow::Cow;
+
+    fn guess(word: &'static str, mask: [Correctness; 5]) -> Guess<'static> {
+        Guess {
+            word: Cow::Borrowed(word),
+            mask,
+        }
+    }
+
+    fn all_masks() -> impl Iterator<Item = [Correctness; 5]> {
+        use Correctness::*;
+        (0..3usize.pow(5)).map(|mut n| {
+            let mut mask = [Wrong; 5];
+            for c in &mut mask {
+                *c = [Correct, Misplaced, Wrong][n % 3];
+                n /= 3;
+            }
+            mask
+        })
+    }
+
+    #[test]
+    fn same_as_matches() {
+        // Every mask, possible or not, for guesses with and without repeated letters.
+        for word in ["tares", "sassy", "geese", "eerie"] {
+            for mask in all_masks() {
+                let guess = guess(word, mask);
+                let constraints = Constraints::new(std::slice::from_ref(&guess));
+                for &(answer, _) in DICTIONARY.iter().step_by(7) {
+                    assert_eq!(
+                        constraints.allows(answer),
+                        guess.matches(answer),
+                        "{} {} for {}",
+                        word,
+                        crate::Pattern(mask),
+                        answer
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn whole_history() {
+        let history = [
+            guess("tares", Correctness::compute("cigar", "tares")),
+            guess("drain", Correctness::compute("cigar", "drain")),
+            guess("rival", Correctness::compute("cigar", "rival")),
+        ];
+        let constraints = Constraints::new(&history);
+        for &(word, _) in DICTIONARY.iter() {
+            assert_eq!(
+                constraints.allows(word),
+                history.iter().all(|g| g.matches(word)),
+                "{}",
+                word
+            );
+        }
+        assert!(constraints.allows("cigar"));
+        assert!(!constraints.is_impossible());
+    }
+
+    #[test]
+    fn impossible() {
+        // The first `s` would have been yellow before the second one.
+        let constraints = Constraints::new(&[guess("sassy", mask![W C M W W])]);
+        assert!(constraints.is_impossible());
+        assert!(!constraints.allows("assts"));
+
+        // Guesses that disagree aren't impossible on their own.
+        let constraints = Constraints::new(&[
+            guess("tares", mask![C W W W W]),
+            guess("tares", mask![W W W W W]),
+        ]);
+        assert!(!constraints.is_impossible());
+        assert!(DICTIONARY.iter().all(|&(w, _)| !constraints.allows(w)));
+    }
+
+    #[test]
+    #[should_panic(expected = "lowercase ASCII")]
+    fn uppercase_guess() {
+        Constraints::new(&[guess("TARES", mask![W M M W W])]);
+    }
+
+    #[test]
+    fn unknown_tiles() {
+        let partial = guess("tares", mask![W M U W W]);
+        let constraints = Constraints::new(std::slice::from_ref(&partial));
+        for &(word, _) in DICTIONARY.iter() {
+            if partial.matches(word) {
+                assert!(constraints.allows(word), "{}", word);
+            }
+        }
+        assert!(Constraints::new(&[guess("tares", mask![U U U U U])]).allows("zzzzz"));
+    }
+
+    fn check_regex(history: &[Guess], words: impl Iterator<Item = &'static str>) {
+        let constraints = Constraints::new(history);
+        let regex = regex::Regex::new(&constraints.regex()).unwrap();
+        let counts = constraints.letter_counts();
+        for word in words {
+            assert_eq!(
+                regex.is_match(word) && counts.iter().all(|c| c.allows(word)),
+                history.iter().all(|g| g.matches(word)),
+                "{} with {:?} for {}",
+                constraints.regex(),
+                counts,
+                word
+            );
+        }
+    }
+
+    #[test]
+    fn regex() {
+        let words = || DICTIONARY.iter().map(|&(word, _)| word);
+        let game = ["tares", "drain", "rival", "cigar"]
+            .map(|g| guess(g, Correctness::compute("cigar", g)));
+        for n in 0..=game.len() {
+            check_regex(&game[..n], words());
+        }
+        let game = ["tares", "geese", "eerie", "sassy", "mummy"]
+            .map(|g| guess(g, Correctness::compute("sleep", g)));
+        for n in 0..=game.len() {
+            check_regex(&game[..n], words());
+        }
+        // Including masks that no word gets.
+        for word in ["sassy", "geese"] {
+            for mask in all_masks() {
+                check_regex(&[guess(word, mask)], words().step_by(11));
+            }
+        }
+
+        let constraints = Constraints::new(&[guess("tares", mask![W C M W W])]);
+        // T, E, and S aren't in the word at all, and R isn't in the middle.
+        assert_eq!(
+            constraints.regex(),
+            "^[a-df-ru-z]a[a-df-qu-z][a-df-ru-z][a-df-ru-z]$"
+        );
+        assert_eq!(
+            constraints.letter_counts(),
+            [LetterCount {
+                letter: 'r',
+                min: 1,
+                max: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn hard_mode() {
+        let constraints = Constraints::new(&[guess("tares", mask![W C M W W])]);
+        assert_eq!(constraints.check_hard_mode("cairn"), Ok(()));
+        // Known-wrong letters and yellow letters in the same spot are fine.
+        assert_eq!(constraints.check_hard_mode("tarot"), Ok(()));
+        assert_eq!(
+            constraints.check_hard_mode("crane"),
+            Err(HardModeError::MissingLetterAt {
+                position: 1,
+                letter: 'a'
+            })
+        );
+        assert_eq!(
+            constraints.check_hard_mode("bawdy"),
+            Err(HardModeError::MissingLetter('r'))
+        );
+        assert_eq!(
+            HardModeError::MissingLetterAt {
+                position: 1,
+                letter: 'a'
+            }
+            .to_string(),
+            "2nd letter must be A"
+        );
+
+        // Letters found twice must be guessed twice.
+        let constraints = Constraints::new(&[guess("geese", mask![W M W W M])]);
+        assert_eq!(constraints.check_hard_mode("eerie"), Ok(()));
+        assert_eq!(
+            constraints.check_hard_mode("crane"),
+            Err(HardModeError::MissingLetter('e'))
+        );
+    }
+}
@@ -1,15 +1,49 @@
 #![allow(clippy::type_complexity)]
-#![allow(clippy::blocks_in_if_conditions)]
+#![allow(clippy::blocks_in_conditions)]
 
 use std::{borrow::Cow, collections::HashSet, num::NonZeroU8};
 
-mod solver;
-pub use solver::{Rank, Solver};
+#[cfg(test)]
+macro_rules! guesser {
+    (|$history:ident| $impl:block) => {{
+        struct G;
+        impl $crate::Guesser for G {
+            fn guess(&mut self, $history: &[Guess]) -> std::borrow::Cow<'static, str> {
+                $impl
+            }
+        }
+        G
+    }};
+}
+
+#[cfg(test)]
+macro_rules! mask {
+    (C) => {$crate::Correctness::Correct};
+    (M) => {$crate::Correctness::Misplaced};
+    (W) => {$crate::Correctness::Wrong};
+    (U) => {$crate::Correctness::Unknown};
+    ($($c:tt)+) => {[
+        $(mask!($c)),+
+    ]}
+}
 
-include!(concat!(env!("OUT_DIR"), "/dictionary.rs"));
+mod constraints;
+mod pattern;
+mod solver;
+pub use constraints::{Constraints, HardModeError, LetterCount};
+pub use pattern::{ParsePatternError, Pattern};
+pub use solver::{Candidate, Evaluation, LuckAndSkill, NoCandidates, Options, Rank, Solver};
+
+// `DICTIONARY.len()` is used in const contexts (like the cache's array type), so it can't be a
+// `static`.
+#[allow(clippy::large_const_arrays)]
+mod dictionary {
+    include!(concat!(env!("OUT_DIR"), "/dictionary.rs"));
+}
+pub use dictionary::DICTIONARY;
 
 pub struct Wordle {
-    dictionary: HashSet<&'static str>,
+    dictionary: HashSet<Cow<'static, str>>,
 }
 
 impl Default for Wordle {
@@ -20,19 +54,33 @@ impl Default for Wordle {
 
 impl Wordle {
     pub fn new() -> Self {
+        Self::with_dictionary(DICTIONARY.iter().map(|&(word, _)| word))
+    }
+
+    /// A game that only accepts guesses from `words`, which can be loaded at runtime.
+    pub fn with_dictionary<I>(words: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Cow<'static, str>>,
+    {
         Self {
-            dictionary: HashSet::from_iter(DICTIONARY.iter().copied().map(|(word, _)| word)),
+            dictionary: words.into_iter().map(Into::into).collect(),
         }
     }
 
-    pub fn play<G: Guesser>(&self, answer: &'static str, mut guesser: G) -> Option<usize> {
+    pub fn play<G: Guesser>(&self, answer: &str, mut guesser: G) -> Option<usize> {
+        guesser.new_game();
         let mut history = Vec::new();
         // Wordle only allows six guesses.
         // We allow more to avoid chopping off the score distribution for stats purposes.
         for i in 1..=32 {
             let guess = guesser.guess(&history);
             if guess == answer {
-                guesser.finish(i);
+                history.push(Guess {
+                    word: guess,
+                    mask: [Correctness::Correct; 5],
+                });
+                guesser.finish(answer, &history, Outcome::Won(i));
                 return Some(i);
             }
             assert!(
@@ -42,10 +90,11 @@ impl Wordle {
             );
             let correctness = Correctness::compute(answer, &guess);
             history.push(Guess {
-                word: Cow::Owned(guess),
+                word: guess,
                 mask: correctness,
             });
         }
+        guesser.finish(answer, &history, Outcome::Lost);
         None
     }
 }
@@ -58,6 +107,10 @@ pub enum Correctness {
     Misplaced,
     /// Gray
     Wrong,
+    /// Could be any color, like when only part of the colors are known.
+    ///
+    /// [`Correctness::compute`] never produces this.
+    Unknown,
 }
 
 impl Correctness {
@@ -100,6 +153,19 @@ impl Correctness {
 
         c
     }
+
+    /// Returns true if guessing `guess` can produce `mask` for any answer in [`DICTIONARY`].
+    ///
+    /// Some masks can never happen, like four greens and one yellow, while others are impossible
+    /// only for particular guesses. For example, with `sassy`, the first `s` would be yellow
+    /// before any of the later ones are.
+    pub fn is_achievable(guess: &str, mask: [Self; 5]) -> bool {
+        let guess = Guess {
+            word: Cow::Borrowed(guess),
+            mask,
+        };
+        DICTIONARY.iter().any(|&(word, _)| guess.matches(word))
+    }
 }
 
 pub const MAX_MASK_ENUM: usize = 3 * 3 * 3 * 3 * 3;
@@ -119,6 +185,9 @@ impl From<[Correctness; 5]> for PackedCorrectness {
                     Correctness::Correct => 0,
                     Correctness::Misplaced => 1,
                     Correctness::Wrong => 2,
+                    Correctness::Unknown => {
+                        unreachable!("masks with unknown tiles can't be packed")
+                    }
                 }
         });
         Self(NonZeroU8::new(packed + 1).unwrap())
@@ -144,6 +213,13 @@ impl Guess<'_> {
         // without _necessarily_ computing the full mask for the tested word
         assert_eq!(word.len(), 5);
         assert_eq!(self.word.len(), 5);
+        if self.mask.contains(&Correctness::Unknown) {
+            // Unknown tiles could be any color, so only compare the ones we do know.
+            return Correctness::compute(word, &self.word)
+                .into_iter()
+                .zip(self.mask)
+                .all(|(c, e)| e == Correctness::Unknown || c == e);
+        }
         let mut used = [false; 5];
 
         // Check Correct letters
@@ -174,35 +250,96 @@ impl Guess<'_> {
 }
 
 pub trait Guesser {
-    fn guess(&mut self, history: &[Guess]) -> String;
-    fn finish(&self, _guesses: usize) {}
+    /// Called before the first guess of every game, so that one guesser can play many games.
+    fn new_game(&mut self) {}
+    /// Returns the word to guess next.
+    ///
+    /// Guessers that pick their words from [`DICTIONARY`] can return them borrowed, which saves
+    /// allocating a `String` for every guess.
+    fn guess(&mut self, history: &[Guess]) -> Cow<'static, str>;
+    /// Called at the end of every game with its answer, every guess that was made, and how the
+    /// game ended.
+    fn finish(&mut self, _answer: &str, _history: &[Guess], _outcome: Outcome) {}
 }
 
-#[cfg(test)]
-macro_rules! guesser {
-    (|$history:ident| $impl:block) => {{
-        struct G;
-        impl $crate::Guesser for G {
-            fn guess(&mut self, $history: &[Guess]) -> String {
-                $impl
-            }
-        }
-        G
-    }};
+/// How a game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The answer was guessed, with this many guesses.
+    Won(usize),
+    /// The guesser ran out of guesses.
+    Lost,
 }
 
-#[cfg(test)]
-macro_rules! mask {
-    (C) => {$crate::Correctness::Correct};
-    (M) => {$crate::Correctness::Misplaced};
-    (W) => {$crate::Correctness::Wrong};
-    ($($c:tt)+) => {[
-        $(mask!($c)),+
-    ]}
+impl<G> Guesser for &mut G
+where
+    G: Guesser + ?Sized,
+{
+    fn new_game(&mut self) {
+        (**self).new_game()
+    }
+    fn guess(&mut self, history: &[Guess]) -> Cow<'static, str> {
+        (**self).guess(history)
+    }
+    fn finish(&mut self, answer: &str, history: &[Guess], outcome: Outcome) {
+        (**self).finish(answer, history, outcome)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    mod unknown {
+        use crate::{Correctness, Guess, DICTIONARY};
+        use std::borrow::Cow;
+
+        fn fits(mask: [Correctness; 5]) -> Vec<&'static str> {
+            let guess = Guess {
+                word: Cow::Borrowed("tares"),
+                mask,
+            };
+            DICTIONARY
+                .iter()
+                .map(|&(word, _)| word)
+                .filter(|word| guess.matches(word))
+                .collect()
+        }
+
+        #[test]
+        fn all_unknown() {
+            assert_eq!(fits(mask![U U U U U]).len(), DICTIONARY.len());
+        }
+
+        #[test]
+        fn only_known_tiles_count() {
+            for word in fits(mask![C U U U U]) {
+                assert!(word.starts_with('t'), "{}", word);
+            }
+            assert!(fits(mask![C U U U U]).contains(&"tiger"));
+            for word in fits(mask![U U U U W]) {
+                assert_eq!(Correctness::compute(word, "tares")[4], Correctness::Wrong);
+            }
+            assert!(fits(mask![U U U U W]).contains(&"cigar"));
+            assert!(!fits(mask![U U U U W]).contains(&"moles"));
+        }
+
+        #[test]
+        fn same_as_any_known_colors() {
+            let mut either = fits(mask![W M C W W]);
+            either.extend(fits(mask![W M M W W]));
+            either.extend(fits(mask![W M W W W]));
+            either.sort_unstable();
+            let mut unknown = fits(mask![W M U W W]);
+            unknown.sort_unstable();
+            assert_eq!(unknown, either);
+        }
+
+        #[test]
+        fn achievable() {
+            assert!(Correctness::is_achievable("tares", mask![C C C C U]));
+            assert!(Correctness::is_achievable("tares", mask![U M C C C]));
+        }
+    }
+
     mod guess_matcher {
         use crate::Guess;
         use std::borrow::Cow;
@@ -256,12 +393,13 @@ mod tests {
         }
     }
     mod game {
-        use crate::{Guess, Wordle};
+        use crate::{Guess, Guesser, Outcome, Wordle};
+        use std::borrow::Cow;
 
         #[test]
         fn genius() {
             let w = Wordle::new();
-            let guesser = guesser!(|_history| { "right".to_string() });
+            let guesser = guesser!(|_history| { "right".into() });
             assert_eq!(w.play("right", guesser), Some(1));
         }
 
@@ -270,9 +408,9 @@ mod tests {
             let w = Wordle::new();
             let guesser = guesser!(|history| {
                 if history.len() == 1 {
-                    return "right".to_string();
+                    return "right".into();
                 }
-                "wrong".to_string()
+                "wrong".into()
             });
             assert_eq!(w.play("right", guesser), Some(2));
         }
@@ -282,9 +420,9 @@ mod tests {
             let w = Wordle::new();
             let guesser = guesser!(|history| {
                 if history.len() == 2 {
-                    return "right".to_string();
+                    return "right".into();
                 }
-                "wrong".to_string()
+                "wrong".into()
             });
             assert_eq!(w.play("right", guesser), Some(3));
         }
@@ -294,9 +432,9 @@ mod tests {
             let w = Wordle::new();
             let guesser = guesser!(|history| {
                 if history.len() == 3 {
-                    return "right".to_string();
+                    return "right".into();
                 }
-                "wrong".to_string()
+                "wrong".into()
             });
             assert_eq!(w.play("right", guesser), Some(4));
         }
@@ -306,9 +444,9 @@ mod tests {
             let w = Wordle::new();
             let guesser = guesser!(|history| {
                 if history.len() == 4 {
-                    return "right".to_string();
+                    return "right".into();
                 }
-                "wrong".to_string()
+                "wrong".into()
             });
             assert_eq!(w.play("right", guesser), Some(5));
         }
@@ -318,9 +456,9 @@ mod tests {
             let w = Wordle::new();
             let guesser = guesser!(|history| {
                 if history.len() == 5 {
-                    return "right".to_string();
+                    return "right".into();
                 }
-                "wrong".to_string()
+                "wrong".into()
             });
             assert_eq!(w.play("right", guesser), Some(6));
         }
@@ -328,9 +466,102 @@ mod tests {
         #[test]
         fn oops() {
             let w = Wordle::new();
-            let guesser = guesser!(|_history| { "wrong".to_string() });
+            let guesser = guesser!(|_history| { "wrong".into() });
             assert_eq!(w.play("right", guesser), None);
         }
+
+        #[test]
+        fn runtime_words() {
+            let answer = String::from("right");
+            let w = Wordle::with_dictionary(vec![String::from("wrong"), answer.clone()]);
+            let guesser = guesser!(|history| {
+                if history.len() == 1 {
+                    return "right".into();
+                }
+                "wrong".into()
+            });
+            assert_eq!(w.play(&answer, guesser), Some(2));
+        }
+
+        #[test]
+        #[should_panic(expected = "not in the dictionary")]
+        fn runtime_words_only() {
+            let w = Wordle::with_dictionary(["right"]);
+            let guesser = guesser!(|_history| { "wrong".into() });
+            w.play("right", guesser);
+        }
+
+        #[test]
+        fn finish() {
+            #[derive(Default)]
+            struct Log(Vec<(String, Vec<String>, Outcome)>);
+            impl Guesser for Log {
+                fn guess(&mut self, history: &[Guess]) -> Cow<'static, str> {
+                    if history.len() == 1 {
+                        return "right".into();
+                    }
+                    "wrong".into()
+                }
+                fn finish(&mut self, answer: &str, history: &[Guess], outcome: Outcome) {
+                    let words = history.iter().map(|g| g.word.to_string()).collect();
+                    self.0.push((answer.to_string(), words, outcome));
+                    assert_eq!(history.last().unwrap().mask, mask![C C C C C]);
+                }
+            }
+
+            let w = Wordle::new();
+            let mut log = Log::default();
+            assert_eq!(w.play("right", &mut log), Some(2));
+            assert_eq!(
+                log.0,
+                [(
+                    "right".into(),
+                    vec!["wrong".into(), "right".into()],
+                    Outcome::Won(2)
+                )]
+            );
+        }
+
+        #[test]
+        fn finish_lost() {
+            struct Lost(Option<(usize, Outcome)>);
+            impl Guesser for Lost {
+                fn guess(&mut self, _history: &[Guess]) -> Cow<'static, str> {
+                    "wrong".into()
+                }
+                fn finish(&mut self, _answer: &str, history: &[Guess], outcome: Outcome) {
+                    self.0 = Some((history.len(), outcome));
+                }
+            }
+
+            let mut lost = Lost(None);
+            assert_eq!(Wordle::new().play("right", &mut lost), None);
+            assert_eq!(lost.0, Some((32, Outcome::Lost)));
+        }
+    }
+
+    mod achievable {
+        use crate::Correctness;
+
+        #[test]
+        fn always() {
+            assert!(Correctness::is_achievable("tares", mask![C C C C C]));
+            assert!(Correctness::is_achievable("tares", mask![W W W W W]));
+            assert!(Correctness::is_achievable("tares", mask![W M M W W]));
+        }
+
+        #[test]
+        fn never() {
+            assert!(!Correctness::is_achievable("tares", mask![C C C C M]));
+            assert!(!Correctness::is_achievable("tares", mask![C C M C C]));
+        }
+
+        #[test]
+        fn repeated_letters() {
+            // A later s can't be yellow if an earlier one is gray.
+            assert!(!Correctness::is_achievable("sassy", mask![W W M W W]));
+            assert!(Correctness::is_achievable("sassy", mask![M W W W W]));
+        }
     }
 
     mod compute {
@@ -1,7 +1,12 @@
 use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-use clap::{ArgEnum, Parser};
-use roget::{Guesser, Solver};
+use clap::{ArgEnum, Parser, Subcommand};
+use roget::{Guess, Guesser, Options, Solver};
+use serde::{Deserialize, Serialize};
 
 const GAMES: &str = include_str!("../answers.txt");
 
@@ -48,6 +53,221 @@ struct Args {
     /// This mode is intended for helping you play the game elsewhere. The program will tell you what word to guess next, and ask you for what combination of correct/misplaced/incorrect you got in return.
     #[clap(short, long, conflicts_with = "games")]
     interactive: bool,
+
+    /// In interactive mode, also show the best few guesses the solver considered, and why.
+    ///
+    /// For each guess this shows the probability that it's the answer, the expected information
+    /// (in bits) it gives, the expected total number of guesses when playing it, how many distinct
+    /// color patterns it can produce, and how many words are left in the worst case.
+    #[clap(long, value_name = "K", requires = "interactive")]
+    explain: Option<usize>,
+
+    /// In interactive mode, also show what's known so far before each guess.
+    ///
+    /// This shows how many words could still be the answer, the N most likely of them, and which
+    /// letters are known to be in the right place, in the wrong place, or not in the word at all.
+    #[clap(long, value_name = "N", requires = "interactive")]
+    remaining: Option<usize>,
+
+    /// Also print the results of the games in a machine-readable format on standard output.
+    ///
+    /// JSON includes every game's guesses along with the score distribution and summary
+    /// statistics. CSV has one row per game with the answer, the score, and the guesses made,
+    /// followed by a blank line and a second table with the same distribution and statistics.
+    #[clap(short, long, arg_enum, conflicts_with = "interactive")]
+    output: Option<Format>,
+
+    /// Play the answers in this file, separated by whitespace, instead of the Wordle answers.
+    ///
+    /// Every answer has to be in the dictionary, since the solver would never guess it otherwise.
+    #[clap(long, value_name = "FILE", conflicts_with_all = &["interactive", "protocol"])]
+    answers: Option<PathBuf>,
+
+    /// Let another program drive the solver through standard input and output.
+    ///
+    /// Each line on standard input is one command, and gets exactly one line of JSON in reply.
+    /// Every reply has an "ok" field, and failed commands have an "error" message. The commands
+    /// are:
+    ///
+    ///  - `new`: start a new game.
+    ///  - `observe WORD COLORS`: record that WORD was guessed and got COLORS (like `WMMWW`).
+    ///    Replies with how many possible answers are left.
+    ///  - `suggest [K]`: reply with the next guess, or with the K best guesses and their scores.
+    ///  - `candidates [N]`: reply with (the N most likely of) the possible answers left.
+    ///  - `reset [N]`: forget everything but the first N (by default 0) observations.
+    #[clap(long, conflicts_with_all = &["interactive", "games", "output"])]
+    protocol: bool,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the games once for every combination of solver options.
+    ///
+    /// This ignores the solver flags given to `roget` itself, and instead tries every combination
+    /// of `--no-sigmoid`, `--rank-by`, `--no-cutoff`, and `--easy`. For each one, it writes a CSV
+    /// row with the average score, the score distribution, the number of failed games, and how
+    /// long the games took to play. The colors of every pair of words are worked out before any
+    /// games are timed, so that the times of different rows can be compared.
+    Sweep {
+        /// The number of games to run for each combination.
+        ///
+        /// If not passed, `--games` given to `roget` itself is used, and otherwise all Wordle games
+        /// (or all the answers given with `--answers`) are run.
+        #[clap(short, long)]
+        games: Option<usize>,
+
+        /// Write the CSV to this file instead of to standard output.
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Compare the per-game results of two runs made with `--output json`.
+    ///
+    /// Lists every answer whose score changed from the first run to the second, the answers that
+    /// only one of the runs failed to guess, and how likely it is that the change in average score
+    /// is just noise.
+    Compare {
+        /// The JSON results of the baseline run.
+        a: PathBuf,
+
+        /// The JSON results of the run to compare against the baseline.
+        b: PathBuf,
+    },
+
+    /// Play the games and report on the answers that the solver found the hardest.
+    ///
+    /// This uses the solver flags, `--games`, and `--answers` given to `roget` itself. It lists the
+    /// answers that took the most guesses along with the guesses that were made, and then groups
+    /// the hard answers by the largest family of dictionary words that differ from them in just
+    /// one letter (like "_ight" or "_ound"), since guessing your way through such a family is the
+    /// most common reason for a game to drag on.
+    Hardest {
+        /// The number of hardest answers to list.
+        #[clap(short = 'n', long, default_value = "20")]
+        top: usize,
+
+        /// Answers that took at least this many guesses, or that were never guessed, are grouped by
+        /// their word family.
+        #[clap(long, default_value = "6")]
+        min_score: usize,
+    },
+
+    /// Print the next guess for a game that's already under way, and exit.
+    ///
+    /// This uses the solver flags given to `roget` itself.
+    Suggest {
+        /// The guesses made so far, each as the word and its colors, like `tares:WMMWW`.
+        ///
+        /// The colors can be written in any notation that interactive mode accepts.
+        #[clap(value_name = "WORD:COLORS")]
+        history: Vec<String>,
+
+        /// Print the K best guesses, and how they scored, as JSON.
+        #[clap(long, value_name = "K")]
+        top: Option<usize>,
+    },
+
+    /// Grade every guess of a game that's been played, compared to what the solver would do.
+    ///
+    /// This uses the solver flags given to `roget` itself. For each guess, it shows how much
+    /// information the guess was expected to give, how many more guesses it was expected to take,
+    /// how it ranked among all the guesses that could have been made, and what the solver would
+    /// have guessed instead.
+    Grade {
+        /// The guesses that were made, each as the word and its colors, like `tares:WMMWW`.
+        #[clap(value_name = "WORD:COLORS", required = true)]
+        game: Vec<String>,
+    },
+
+    /// Split a finished game into how much skill and how much luck went into each guess.
+    ///
+    /// This uses the solver flags given to `roget` itself. Skill is how much more information
+    /// (in bits) a guess was expected to give than the guess the solver would have made, and luck
+    /// is how much more information the guess gave than it was expected to.
+    Luck {
+        /// The answer of the game.
+        answer: String,
+
+        /// The words that were guessed, in order, ending with the answer.
+        #[clap(required = true)]
+        guesses: Vec<String>,
+    },
+
+    /// List the words in the dictionary that fit a pattern, most common first.
+    ///
+    /// This doesn't solve anything, and is more like looking up a crossword clue.
+    Find {
+        /// The letters of the word, with `_`, `?`, or `.` for the ones that can be anything, like
+        /// `_r_a_`.
+        pattern: String,
+
+        /// Letters the word must contain. Repeat a letter to require it more than once.
+        #[clap(short, long, default_value = "")]
+        include: String,
+
+        /// Letters the word must not contain.
+        #[clap(short = 'x', long, default_value = "")]
+        exclude: String,
+
+        /// The number of words to list.
+        #[clap(short = 'n', long)]
+        top: Option<usize>,
+    },
+
+    /// Print a regular expression and letter counts that match exactly the words that fit the
+    /// guesses made so far.
+    ///
+    /// Use this to search your own word lists. Each letter count is printed on its own line after
+    /// the regular expression. Every color has to be known, since what a tile of unknown color
+    /// rules out depends on the other tiles in ways a regular expression can't capture.
+    Regex {
+        /// The guesses made so far, each as the word and its colors, like `tares:WMMWW`.
+        #[clap(value_name = "WORD:COLORS")]
+        history: Vec<String>,
+
+        /// Print a `grep -E` pipeline that filters a word list given on standard input instead.
+        #[clap(long)]
+        grep: bool,
+    },
+
+    /// Work out the answer of the day from the color grids that players shared.
+    ///
+    /// Each grid is one player's game, with the rows as in `suggest` separated by spaces, commas,
+    /// slashes, or newlines. Lines starting with "Wordle", like the header of a shared result, are
+    /// ignored. If no grids are given, they're read from standard input, separated by blank lines.
+    /// Prints the answers that fit every grid, most likely first.
+    Reverse {
+        /// The color grids of the games.
+        #[clap(value_name = "GRID")]
+        grids: Vec<String>,
+
+        /// The number of answers to list.
+        #[clap(short = 'n', long, default_value = "20")]
+        top: usize,
+    },
+
+    /// Answer requests for suggestions over HTTP with JSON, on localhost only.
+    ///
+    /// This uses the solver flags given to `roget` itself. Every endpoint takes the guesses made so
+    /// far as `history=tares:WMMWW,drain:WMMMW`, and builds a solver for just that request:
+    ///
+    ///  - `GET /suggest?history=..[&top=K]` gives the next guess, or the K best guesses.
+    ///  - `GET /candidates?history=..[&n=N]` lists the (N most likely) possible answers.
+    ///  - `GET /score?history=..&guess=WORD` shows how good a guess WORD would be.
+    Serve {
+        /// The port to listen on.
+        #[clap(long, default_value = "8000")]
+        port: u16,
+    },
+}
+
+#[derive(ArgEnum, Debug, Clone, Copy)]
+enum Format {
+    Json,
+    Csv,
 }
 
 #[derive(ArgEnum, Debug, Clone, Copy)]
@@ -68,6 +288,18 @@ enum Rank {
     ExpectedInformation,
 }
 
+impl From<Rank> for roget::Rank {
+    fn from(rank: Rank) -> Self {
+        match rank {
+            Rank::First => roget::Rank::First,
+            Rank::ExpectedScore => roget::Rank::ExpectedScore,
+            Rank::WeightedInformation => roget::Rank::WeightedInformation,
+            Rank::InfoPlusProbability => roget::Rank::InfoPlusProbability,
+            Rank::ExpectedInformation => roget::Rank::ExpectedInformation,
+        }
+    }
+}
+
 fn main() {
     let args = Args::parse();
 
@@ -84,99 +316,1404 @@ fn main() {
     if args.easy {
         solver.hard_mode = false;
     }
-    solver.rank_by = match args.rank_by {
-        Rank::First => roget::Rank::First,
-        Rank::ExpectedScore => roget::Rank::ExpectedScore,
-        Rank::WeightedInformation => roget::Rank::WeightedInformation,
-        Rank::InfoPlusProbability => roget::Rank::InfoPlusProbability,
-        Rank::ExpectedInformation => roget::Rank::ExpectedInformation,
+    solver.rank_by = args.rank_by.into();
+    let answers = match &args.answers {
+        Some(path) => Cow::Owned(load_answers(path).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        })),
+        None => Cow::Borrowed(GAMES),
     };
-    if args.interactive {
-        play_interactive(solver.build());
-    } else {
-        play(move || solver.build(), args.games);
+    match args.command {
+        Some(Command::Sweep { games, output }) => {
+            if let Err(e) = sweep(&answers, games.or(args.games), output) {
+                eprintln!("could not write sweep results: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Compare { a, b }) => {
+            let load = |path: PathBuf| {
+                let games = std::fs::File::open(&path)
+                    .map_err(serde_json::Error::io)
+                    .and_then(|f| serde_json::from_reader(std::io::BufReader::new(f)))
+                    .map(|saved: SavedBenchmark| saved.games);
+                games.unwrap_or_else(|e| {
+                    eprintln!("could not read results from {}: {}", path.display(), e);
+                    std::process::exit(1);
+                })
+            };
+            compare(&load(a), &load(b));
+        }
+        Some(Command::Suggest { history, top }) => {
+            let history = parse_history(&history).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            });
+            if let Err(e) = suggest(solver.build(), &history, top) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Grade { game }) => {
+            let game = parse_history(&game).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            });
+            if let Err(e) = grade(solver.build(), &game) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Luck { answer, guesses }) => {
+            let answer = answer.to_ascii_lowercase();
+            let guesses: Vec<_> = guesses.iter().map(|g| g.to_ascii_lowercase()).collect();
+            if let Err(e) = check_finished_game(&answer, &guesses) {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+            if let Err(e) = luck(solver.build(), &answer, &guesses) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Find {
+            pattern,
+            include,
+            exclude,
+            top,
+        }) => {
+            let words = find(&pattern, &include, &exclude).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            });
+            let total: usize = roget::DICTIONARY.iter().map(|&(_, count)| count).sum();
+            for (word, count) in words.into_iter().take(top.unwrap_or(usize::MAX)) {
+                println!(
+                    "{}  {:>10}  {:.6}%",
+                    word,
+                    count,
+                    100.0 * count as f64 / total as f64
+                );
+            }
+        }
+        Some(Command::Regex { history, grep }) => {
+            let history = parse_known_history(&history).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            });
+            let constraints = roget::Constraints::new(&history);
+            if grep {
+                println!("{}", grep_pipeline(&constraints));
+            } else {
+                println!("{}", constraints.regex());
+                for count in constraints.letter_counts() {
+                    println!("{}", describe_count(count));
+                }
+            }
+        }
+        Some(Command::Reverse { grids, top }) => {
+            let grids = if grids.is_empty() {
+                let mut input = String::new();
+                if let Err(e) = std::io::Read::read_to_string(&mut std::io::stdin(), &mut input) {
+                    eprintln!("could not read the grids: {}", e);
+                    std::process::exit(1);
+                }
+                input
+                    .replace("\r\n", "\n")
+                    .split("\n\n")
+                    .filter(|grid| !grid.trim().is_empty())
+                    .map(String::from)
+                    .collect()
+            } else {
+                grids
+            };
+            let grids = grids
+                .iter()
+                .map(|grid| parse_grid(grid))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                });
+            reverse(solver.build(), &grids, top);
+        }
+        Some(Command::Serve { port }) => {
+            if let Err(e) = serve(solver, port) {
+                eprintln!("could not serve on port {}: {}", port, e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Hardest { top, min_score }) => {
+            hardest(solver.build(), &answers, args.games, top, min_score)
+        }
+        None if args.interactive => play_interactive(solver, args.explain, args.remaining),
+        None if args.protocol => run_protocol(solver),
+        None => play(solver.build(), &answers, args.games, args.output),
     }
 }
 
-fn play_interactive(mut guesser: impl Guesser) {
+/// Something the user can answer with in interactive mode.
+enum Response {
+    /// The colors the last guess got.
+    Colors([roget::Correctness; 5]),
+    /// Forget the colors given for the previous guess.
+    Undo,
+    /// Forget all the guesses so far.
+    Restart,
+    /// Change the colors given for the guess with this (1-based) number.
+    Edit(usize),
+    /// The game did not accept the guessed word.
+    Rejected,
+}
+
+fn play_interactive(options: Options, explain: Option<usize>, remaining: Option<usize>) {
+    let mut solver = options.build();
     let mut history = Vec::with_capacity(6);
-    println!("C: Correct / Green, M: Misplaced / Yellow, W: Wrong / Gray");
+    println!("C/G/🟩: Correct / Green, M/Y/🟨: Misplaced / Yellow, W/B/⬛: Wrong / Gray");
+    println!("If you don't know the color of a tile, use `?` for it.");
+    println!("To fix a mistake, type `undo`, `restart`, or `edit N` instead of the colors.");
+    println!("If the game doesn't accept a word, type `!` to get a different one.");
     // Wordle only allows six guesses.
-    for _ in 1..=6 {
-        let guess = guesser.guess(&history);
-        println!("Guess:  {}", guess.to_uppercase());
-        let correctness = {
+    while history.len() < 6 {
+        // If no word fits the colors given so far, the user must have made a mistake somewhere.
+        // The most likely culprit is the guess that ruled out the last words, so we ask for that
+        // guess' colors again rather than the colors for a new guess.
+        let guess = match solver.try_guess(&history) {
+            Ok(guess) => {
+                if let Some(n) = remaining.filter(|_| !history.is_empty()) {
+                    print_remaining(
+                        &solver
+                            .possible_answers(&history)
+                            .expect("the solver just found a guess"),
+                        n,
+                    );
+                    print_keyboard(&history);
+                }
+                if let Some(k) = explain {
+                    print_candidates(
+                        &solver
+                            .rank_candidates(&history, k)
+                            .expect("the solver just found a guess"),
+                    );
+                }
+                println!("Guess:  {}", guess.to_uppercase());
+                Ok(guess)
+            }
+            Err(e) => {
+                println!("Oops, {}.", e);
+                println!("Enter the correct colors for guess #{}:", e.step + 1);
+                Err(e.step)
+            }
+        };
+        let colors_for = match &guess {
+            Ok(guess) => &**guess,
+            Err(step) => &*history[*step].word,
+        };
+        let response = {
             loop {
-                match ask_for_correctness() {
-                    Ok(c) => break c,
+                match ask_for_response(colors_for) {
+                    Ok(r) => break r,
                     Err(e) => println!("{}", e),
                 }
             }
         };
-        if correctness == [roget::Correctness::Correct; 5] {
-            println!("The answer was {}", guess.to_uppercase());
-            return;
+        match response {
+            Response::Colors(correctness) => match guess {
+                Ok(guess) => {
+                    if correctness == [roget::Correctness::Correct; 5] {
+                        println!("The answer was {}", guess.to_uppercase());
+                        return;
+                    }
+                    history.push(roget::Guess {
+                        word: Cow::Borrowed(guess),
+                        mask: correctness,
+                    });
+                    continue;
+                }
+                Err(step) => {
+                    if correctness == [roget::Correctness::Correct; 5] {
+                        println!("The answer was {}", history[step].word.to_uppercase());
+                        return;
+                    }
+                    history[step].mask = correctness;
+                }
+            },
+            Response::Undo => {
+                if history.pop().is_none() {
+                    println!("There is nothing to undo.");
+                    continue;
+                }
+            }
+            Response::Restart => history.clear(),
+            Response::Rejected => match guess {
+                Ok(guess) if solver.ban(guess) => {}
+                Ok(guess) => println!(
+                    "{} is the only word left that fits the colors so far, \
+                        so one of them must be wrong.",
+                    guess.to_uppercase()
+                ),
+                Err(_) => println!("There is no word to reject."),
+            },
+            Response::Edit(n) => {
+                let Some(earlier) = n.checked_sub(1).and_then(|i| history.get_mut(i)) else {
+                    println!("There is no guess #{} to edit.", n);
+                    continue;
+                };
+                println!("Guess {}: {}", n, earlier.word.to_uppercase());
+                earlier.mask = loop {
+                    match ask_for_correctness(&earlier.word) {
+                        Ok(c) => break c,
+                        Err(e) => println!("{}", e),
+                    }
+                };
+            }
         }
-        history.push(roget::Guess {
-            word: Cow::Owned(guess),
-            mask: correctness,
-        });
     }
     println!("Game Over, only six guesses are allowed");
 }
 
-fn ask_for_correctness() -> Result<[roget::Correctness; 5], Cow<'static, str>> {
-    print!("Colors: ");
-    std::io::Write::flush(&mut std::io::stdout()).unwrap();
-    let mut answer = String::with_capacity(7);
-    std::io::stdin().read_line(&mut answer).unwrap();
-    let answer = answer
-        .trim()
-        .chars()
-        .filter(|v| !v.is_whitespace())
-        .map(|v| v.to_ascii_uppercase())
-        .collect::<String>();
-    if answer.len() != 5 {
-        Err("You did not provide exactly 5 colors.")?;
+fn print_remaining(possible: &[(&str, f64)], n: usize) {
+    let s = if possible.len() == 1 { "" } else { "s" };
+    println!("{} possible answer{} left", possible.len(), s);
+    for (word, p) in possible.iter().take(n) {
+        println!("  {} {:>5.1}%", word, 100.0 * p);
+    }
+}
+
+/// What the history tells us about each letter, indexed by the letter's offset from `a`.
+///
+/// A letter is `Correct` if it has been green anywhere, `Misplaced` if it has only been yellow,
+/// and `Wrong` if it has only ever been gray.
+fn letter_knowledge(history: &[Guess]) -> [Option<roget::Correctness>; 26] {
+    use roget::Correctness;
+
+    let mut known = [None; 26];
+    for guess in history {
+        for (letter, c) in guess.word.bytes().zip(guess.mask) {
+            let k = &mut known[usize::from(letter - b'a')];
+            *k = match (*k, c) {
+                (Some(Correctness::Correct), _) | (_, Correctness::Correct) => {
+                    Some(Correctness::Correct)
+                }
+                (Some(Correctness::Misplaced), _) | (_, Correctness::Misplaced) => {
+                    Some(Correctness::Misplaced)
+                }
+                (_, Correctness::Wrong) => Some(Correctness::Wrong),
+                (k, Correctness::Unknown) => k,
+            };
+        }
+    }
+    known
+}
+
+fn print_keyboard(history: &[Guess]) {
+    let known = letter_knowledge(history);
+    for (indent, row) in ["qwertyuiop", "asdfghjkl", "zxcvbnm"]
+        .into_iter()
+        .enumerate()
+    {
+        let keys: String = row
+            .bytes()
+            .map(|letter| {
+                let key = char::from(letter.to_ascii_uppercase());
+                match known[usize::from(letter - b'a')] {
+                    Some(roget::Correctness::Correct) => format!("[{}]", key),
+                    Some(roget::Correctness::Misplaced) => format!("({})", key),
+                    Some(roget::Correctness::Wrong) => String::from(" · "),
+                    Some(roget::Correctness::Unknown) | None => format!(" {} ", key),
+                }
+            })
+            .collect();
+        println!("  {}{}", " ".repeat(indent), keys.trim_end());
+    }
+    println!("  [X]: right place, (X): wrong place, ·: not in the word");
+}
+
+fn print_candidates(candidates: &[roget::Candidate]) {
+    println!(
+        "{:<5}  {:>7}  {:>7}  {:>8}  {:>8}  {:>7}",
+        "word", "p(word)", "E[info]", "E[score]", "patterns", "largest"
+    );
+    for c in candidates {
+        println!(
+            "{:<5}  {:>7.4}  {:>7.3}  {:>8.3}  {:>8}  {:>7}",
+            c.word, c.p_word, c.e_info, c.e_score, c.patterns, c.largest_pattern
+        );
+    }
+}
+
+/// Parses a guess and the colors it got, written like `tares:WMMWW`.
+fn parse_guess(entry: &str) -> Result<Guess<'static>, String> {
+    let (word, colors) = entry.split_once(':').ok_or_else(|| {
+        format!(
+            "'{}' should be a word and its colors, like tares:WMMWW",
+            entry
+        )
+    })?;
+    let word = word.trim().to_ascii_lowercase();
+    if word.len() != 5 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return Err(format!("'{}' is not a five-letter word", word));
     }
-    let parsed = answer
+    let pattern = colors
+        .parse::<roget::Pattern>()
+        .map_err(|e| format!("bad colors for '{}': {}", word, e))?;
+    Ok(Guess {
+        word: Cow::Owned(word),
+        mask: pattern.into(),
+    })
+}
+
+fn parse_history<S: AsRef<str>>(entries: &[S]) -> Result<Vec<Guess<'static>>, String> {
+    entries.iter().map(|e| parse_guess(e.as_ref())).collect()
+}
+
+fn candidates_json(candidates: &[roget::Candidate]) -> serde_json::Value {
+    candidates
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "word": c.word,
+                "p_word": c.p_word,
+                "e_info": c.e_info,
+                "e_score": c.e_score,
+                "patterns": c.patterns,
+                "largest_pattern": c.largest_pattern,
+            })
+        })
+        .collect()
+}
+
+fn suggest(
+    mut solver: Solver,
+    history: &[Guess],
+    top: Option<usize>,
+) -> Result<(), roget::NoCandidates> {
+    match top {
+        Some(k) => {
+            let candidates = solver.rank_candidates(history, k)?;
+            println!("{}", candidates_json(&candidates));
+        }
+        None => println!("{}", solver.try_guess(history)?),
+    }
+    Ok(())
+}
+
+/// The state of a game being driven through `--protocol`.
+struct Protocol {
+    solver: Solver,
+    history: Vec<Guess<'static>>,
+}
+
+impl Protocol {
+    fn new(options: Options) -> Self {
+        Self {
+            solver: options.build(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Handles one line of input, and returns the reply, if any.
+    fn handle(&mut self, line: &str) -> Option<serde_json::Value> {
+        use serde_json::json;
+
+        let mut words = line.split_whitespace();
+        let command = words.next()?;
+        let args: Vec<_> = words.collect();
+        let count = |i: usize| -> Result<Option<usize>, String> {
+            args.get(i)
+                .map(|n| n.parse().map_err(|_| format!("'{}' is not a number", n)))
+                .transpose()
+        };
+        let contradiction =
+            |e: roget::NoCandidates| json!({ "ok": false, "error": e.to_string(), "step": e.step });
+        let error = |e: String| json!({ "ok": false, "error": e });
+
+        let reply = match (command, args.len()) {
+            ("new", 0) => {
+                self.history.clear();
+                self.solver.reset();
+                json!({ "ok": true })
+            }
+            ("observe", 2) => match parse_guess(&format!("{}:{}", args[0], args[1])) {
+                Ok(guess) => {
+                    self.history.push(guess);
+                    match self.solver.possible_answers(&self.history) {
+                        Ok(possible) => json!({ "ok": true, "remaining": possible.len() }),
+                        Err(e) => contradiction(e),
+                    }
+                }
+                Err(e) => error(e),
+            },
+            ("suggest", 0) => match self.solver.try_guess(&self.history) {
+                Ok(guess) => json!({ "ok": true, "guess": guess }),
+                Err(e) => contradiction(e),
+            },
+            ("suggest", 1) => match count(0) {
+                Ok(k) => match self.solver.rank_candidates(&self.history, k.unwrap()) {
+                    Ok(candidates) => {
+                        json!({ "ok": true, "guesses": candidates_json(&candidates) })
+                    }
+                    Err(e) => contradiction(e),
+                },
+                Err(e) => error(e),
+            },
+            ("candidates", 0 | 1) => match count(0) {
+                Ok(n) => match self.solver.possible_answers(&self.history) {
+                    Ok(possible) => json!({
+                        "ok": true,
+                        "remaining": possible.len(),
+                        "candidates": possible
+                            .iter()
+                            .take(n.unwrap_or(usize::MAX))
+                            .map(|&(word, p)| json!({ "word": word, "p": p }))
+                            .collect::<Vec<_>>(),
+                    }),
+                    Err(e) => contradiction(e),
+                },
+                Err(e) => error(e),
+            },
+            ("reset", 0 | 1) => match count(0) {
+                Ok(n) => {
+                    self.history.truncate(n.unwrap_or(0));
+                    json!({ "ok": true, "observed": self.history.len() })
+                }
+                Err(e) => error(e),
+            },
+            ("new" | "observe" | "suggest" | "candidates" | "reset", _) => {
+                error(format!("wrong number of arguments for '{}'", command))
+            }
+            _ => error(format!("unknown command '{}'", command)),
+        };
+        Some(reply)
+    }
+}
+
+fn run_protocol(options: Options) {
+    use std::io::BufRead;
+
+    let mut protocol = Protocol::new(options);
+    for line in std::io::stdin().lock().lines() {
+        let line = line.expect("standard input should be readable");
+        if let Some(reply) = protocol.handle(&line) {
+            println!("{}", reply);
+        }
+    }
+}
+
+fn grade(mut solver: Solver, game: &[Guess]) -> Result<(), roget::NoCandidates> {
+    for (i, guess) in game.iter().enumerate() {
+        let history = &game[..i];
+        let word = guess.word.to_uppercase();
+        match solver.evaluate_guess(history, &guess.word)? {
+            Some(e) => {
+                println!(
+                    "#{} {}: {:.2} bits, {:.2} guesses left expected, ranked {} of {}",
+                    i + 1,
+                    word,
+                    e.guess.e_info,
+                    e.e_guesses_left,
+                    e.rank,
+                    e.out_of
+                );
+                if e.best.word == guess.word {
+                    println!("   the same guess as roget");
+                } else {
+                    println!(
+                        "   roget guesses {}: {:.2} more bits, {:.2} fewer guesses expected",
+                        e.best.word.to_uppercase(),
+                        e.info_gap(),
+                        e.score_gap()
+                    );
+                }
+                let biggest: Vec<_> = e
+                    .buckets
+                    .iter()
+                    .take(3)
+                    .map(|&(mask, n)| format!("{} {}", roget::Pattern(mask), n))
+                    .collect();
+                println!(
+                    "   {} possible colors, most common: {}",
+                    e.buckets.len(),
+                    biggest.join(", ")
+                );
+            }
+            None => println!("#{} {}: not a word roget knows", i + 1, word),
+        }
+
+        let left = solver.possible_answers(&game[..=i])?.len();
+        println!(
+            "   got {}, leaving {} possible answer{}",
+            roget::Pattern(guess.mask),
+            left,
+            if left == 1 { "" } else { "s" }
+        );
+        if guess.mask == [roget::Correctness::Correct; 5] {
+            println!("solved in {}", i + 1);
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `guesses` make up a whole game that ended by guessing `answer`.
+fn check_finished_game(answer: &str, guesses: &[String]) -> Result<(), String> {
+    if let Some(word) = std::iter::once(answer)
+        .chain(guesses.iter().map(|g| &**g))
+        .find(|word| word.len() != 5 || !word.bytes().all(|b| b.is_ascii_lowercase()))
+    {
+        return Err(format!("'{}' is not a five-letter word", word));
+    }
+    if !roget::DICTIONARY.iter().any(|&(word, _)| word == answer) {
+        return Err(format!("the answer '{}' is not in the dictionary", answer));
+    }
+    if !guesses.iter().any(|g| g == answer) {
+        return Err(format!(
+            "the answer '{}' was never guessed, so the game isn't finished",
+            answer
+        ));
+    }
+    Ok(())
+}
+
+fn luck(mut solver: Solver, answer: &str, guesses: &[String]) -> Result<(), roget::NoCandidates> {
+    let report = solver.luck_and_skill(answer, guesses)?;
+    println!(
+        "{:<5}  {:<6}  {:>7}  {:>5}  {:>7}  {:>6}  {:>6}  {:>6}",
+        "guess", "colors", "E[info]", "roget", "E[info]", "info", "skill", "luck"
+    );
+    for step in &report {
+        println!(
+            "{:<5}  {:<6}  {:>7.2}  {:>5}  {:>7.2}  {:>6.2}  {:>+6.2}  {:>+6.2}",
+            step.word,
+            roget::Pattern(step.mask).to_string(),
+            step.e_info,
+            step.best,
+            step.best_e_info,
+            step.info,
+            step.skill(),
+            step.luck()
+        );
+    }
+    let skill: f64 = report.iter().map(|step| step.skill()).sum();
+    let luck: f64 = report.iter().map(|step| step.luck()).sum();
+    println!(
+        "total skill: {:+.2} bits, total luck: {:+.2} bits",
+        skill, luck
+    );
+    if report.len() < guesses.len() {
+        println!(
+            "the answer was guessed after {} guesses, so the rest were ignored",
+            report.len()
+        );
+    }
+    Ok(())
+}
+
+/// Finds the words in the dictionary that fit `pattern` and have all the letters in `include` and
+/// none of those in `exclude`, along with how common they are, most common first.
+fn find(pattern: &str, include: &str, exclude: &str) -> Result<Vec<(&'static str, usize)>, String> {
+    let pattern: Vec<_> = pattern
+        .to_ascii_lowercase()
         .chars()
         .map(|c| match c {
-            'C' => Ok(roget::Correctness::Correct),
-            'M' => Ok(roget::Correctness::Misplaced),
-            'W' => Ok(roget::Correctness::Wrong),
-            _ => Err(format!(
-                "The guess color '{c}' wasn't recognized: use C/M/W"
-            )),
+            '_' | '?' | '.' => Ok(None),
+            'a'..='z' => Ok(Some(c)),
+            c => Err(format!("'{}' is neither a letter nor `_`", c)),
         })
-        .collect::<Result<Vec<_>, _>>()?;
-    Ok(parsed
-        .try_into()
-        .expect("The parsed correctness is checked to be 5 items long"))
+        .collect::<Result<_, _>>()?;
+    if pattern.len() != 5 {
+        return Err(format!(
+            "the pattern should have 5 letters, not {}",
+            pattern.len()
+        ));
+    }
+    let letters = |letters: &str, what: &str| -> Result<[usize; 26], String> {
+        let mut counts = [0; 26];
+        for c in letters.to_ascii_lowercase().chars() {
+            if !c.is_ascii_lowercase() {
+                return Err(format!(
+                    "'{}' in the letters to {} is not a letter",
+                    c, what
+                ));
+            }
+            counts[usize::from(c as u8 - b'a')] += 1;
+        }
+        Ok(counts)
+    };
+    let include = letters(include, "include")?;
+    let exclude = letters(exclude, "exclude")?;
+    if let Some(l) = (0..26).find(|&l| include[l] > 0 && exclude[l] > 0) {
+        return Err(format!(
+            "'{}' can't be both included and excluded",
+            char::from(b'a' + l as u8)
+        ));
+    }
+
+    let mut words: Vec<_> = roget::DICTIONARY
+        .iter()
+        .copied()
+        .filter(|&(word, _)| {
+            let mut counts = [0; 26];
+            for (c, p) in word.chars().zip(&pattern) {
+                if p.is_some_and(|p| p != c) {
+                    return false;
+                }
+                counts[usize::from(c as u8 - b'a')] += 1;
+            }
+            (0..26).all(|l| counts[l] >= include[l] && (exclude[l] == 0 || counts[l] == 0))
+        })
+        .collect();
+    // The dictionary is already sorted this way, but it doesn't hurt to make sure.
+    words.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    Ok(words)
 }
 
-fn play<G>(mut mk: impl FnMut() -> G, max: Option<usize>)
-where
-    G: Guesser,
-{
-    let w = roget::Wordle::new();
-    let mut score = 0;
-    let mut games = 0;
-    let mut histogram = Vec::new();
-    for answer in GAMES.split_whitespace().take(max.unwrap_or(usize::MAX)) {
-        let guesser = (mk)();
-        if let Some(s) = w.play(answer, guesser) {
-            games += 1;
-            score += s;
+fn describe_count(count: roget::LetterCount) -> String {
+    let bounds = match (count.min, count.max) {
+        (min, max) if min == max => format!("exactly {}", min),
+        (min, 5) => format!("at least {}", min),
+        (0, max) => format!("at most {}", max),
+        (min, max) => format!("between {} and {}", min, max),
+    };
+    format!("{}: {}", count.letter, bounds)
+}
+
+/// Like [`parse_history`], but rejects tiles of unknown color.
+fn parse_known_history<S: AsRef<str>>(entries: &[S]) -> Result<Vec<Guess<'static>>, String> {
+    let history = parse_history(entries)?;
+    if let Some(guess) = history
+        .iter()
+        .find(|g| g.mask.contains(&roget::Correctness::Unknown))
+    {
+        return Err(format!(
+            "the colors for '{}' must all be known, as ? tiles can't be matched exactly",
+            guess.word
+        ));
+    }
+    Ok(history)
+}
+
+/// Returns a shell pipeline of `grep`s that only lets through the words that fit `constraints`.
+fn grep_pipeline(constraints: &roget::Constraints) -> String {
+    let mut pipeline = format!("grep -E '{}'", constraints.regex());
+    for count in constraints.letter_counts() {
+        // Having `n` of a letter is the same as matching it followed by anything `n` times.
+        if count.min > 0 {
+            pipeline.push_str(&format!(
+                " | grep -E '({}.*){{{}}}'",
+                count.letter, count.min
+            ));
+        }
+        if count.max < 5 {
+            pipeline.push_str(&format!(
+                " | grep -vE '({}.*){{{}}}'",
+                count.letter,
+                count.max + 1
+            ));
+        }
+    }
+    pipeline
+}
+
+/// Parses the colors of every guess of a shared game.
+fn parse_grid(grid: &str) -> Result<Vec<[roget::Correctness; 5]>, String> {
+    grid.lines()
+        .filter(|line| !line.trim_start().to_ascii_lowercase().starts_with("wordle"))
+        .flat_map(|line| line.split(|c: char| c == ',' || c == '/' || c.is_whitespace()))
+        .filter(|row| !row.is_empty())
+        .map(|row| {
+            row.parse::<roget::Pattern>()
+                .map(Into::into)
+                .map_err(|e| format!("bad row '{}': {}", row, e))
+        })
+        .collect()
+}
+
+fn reverse(solver: Solver, grids: &[Vec<[roget::Correctness; 5]>], top: usize) {
+    let possible = solver.reverse_solve(grids);
+    match possible.len() {
+        0 => println!("no answer fits all the grids"),
+        1 => println!("1 answer fits all the grids:"),
+        n => println!("{} answers fit all the grids:", n),
+    }
+    for &(word, p) in possible.iter().take(top) {
+        println!("{}  {:>6.2}%", word, 100.0 * p);
+    }
+}
+
+/// Answers one `serve` request for `url`, with the HTTP status code and the reply.
+fn api(options: Options, url: &str) -> (u16, serde_json::Value) {
+    use serde_json::json;
+
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let mut params = HashMap::new();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        params.insert(percent_decode(key), percent_decode(value));
+    }
+    let error = |e: String| (400, json!({ "ok": false, "error": e }));
+    let contradiction = |e: roget::NoCandidates| {
+        (
+            400,
+            json!({ "ok": false, "error": e.to_string(), "step": e.step }),
+        )
+    };
+    let count = |key: &str| -> Result<Option<usize>, String> {
+        params
+            .get(key)
+            .map(|n| n.parse().map_err(|_| format!("'{}' is not a number", n)))
+            .transpose()
+    };
+
+    if !matches!(path, "/suggest" | "/candidates" | "/score") {
+        return (
+            404,
+            json!({ "ok": false, "error": format!("unknown endpoint '{}'", path) }),
+        );
+    }
+    let history: Vec<_> = params
+        .get("history")
+        .map(|history| history.split(',').filter(|e| !e.is_empty()).collect())
+        .unwrap_or_default();
+    let history = match parse_history(&history) {
+        Ok(history) => history,
+        Err(e) => return error(e),
+    };
+    let mut solver = options.build();
+
+    match path {
+        "/suggest" => match count("top") {
+            Ok(None) => match solver.try_guess(&history) {
+                Ok(guess) => (200, json!({ "ok": true, "guess": guess })),
+                Err(e) => contradiction(e),
+            },
+            Ok(Some(k)) => match solver.rank_candidates(&history, k) {
+                Ok(candidates) => (
+                    200,
+                    json!({ "ok": true, "guesses": candidates_json(&candidates) }),
+                ),
+                Err(e) => contradiction(e),
+            },
+            Err(e) => error(e),
+        },
+        "/candidates" => match count("n") {
+            Ok(n) => match solver.possible_answers(&history) {
+                Ok(possible) => (
+                    200,
+                    json!({
+                        "ok": true,
+                        "remaining": possible.len(),
+                        "candidates": possible
+                            .iter()
+                            .take(n.unwrap_or(usize::MAX))
+                            .map(|&(word, p)| json!({ "word": word, "p": p }))
+                            .collect::<Vec<_>>(),
+                    }),
+                ),
+                Err(e) => contradiction(e),
+            },
+            Err(e) => error(e),
+        },
+        _ => {
+            let Some(guess) = params.get("guess") else {
+                return error("missing the 'guess' to score".to_string());
+            };
+            let guess = guess.to_ascii_lowercase();
+            match solver.score_guess(&history, &guess) {
+                Ok(Some(candidate)) => (
+                    200,
+                    json!({ "ok": true, "guess": candidates_json(&[candidate])[0] }),
+                ),
+                Ok(None) => error(format!("'{}' is not a word roget knows", guess)),
+                Err(e) => contradiction(e),
+            }
+        }
+    }
+}
+
+/// Decodes a `application/x-www-form-urlencoded` query string component.
+fn percent_decode(s: &str) -> String {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => match rest
+                .get(..2)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(decoded) => {
+                    bytes.push(decoded);
+                    rest = &rest[2..];
+                }
+                None => bytes.push(b'%'),
+            },
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn serve(options: Options, port: u16) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let server = tiny_http::Server::http(("127.0.0.1", port))?;
+    eprintln!("listening on http://127.0.0.1:{}", port);
+    let content_type = tiny_http::Header::from_bytes("Content-Type", "application/json")
+        .expect("the header is valid");
+    // Requests are handled one at a time on this thread, so that they all share the pattern
+    // cache, which is built per thread.
+    for request in server.incoming_requests() {
+        let (status, reply) = if *request.method() == tiny_http::Method::Get {
+            api(options, request.url())
+        } else {
+            (
+                405,
+                serde_json::json!({ "ok": false, "error": "only GET requests are supported" }),
+            )
+        };
+        let response = tiny_http::Response::from_string(reply.to_string())
+            .with_status_code(status)
+            .with_header(content_type.clone());
+        if let Err(e) = request.respond(response) {
+            eprintln!("could not respond: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn read_line(prompt: &str) -> String {
+    print!("{}", prompt);
+    std::io::Write::flush(&mut std::io::stdout()).unwrap();
+    let mut line = String::with_capacity(7);
+    if std::io::stdin().read_line(&mut line).unwrap() == 0 {
+        // Standard input was closed, so there is no way for the game to continue.
+        std::process::exit(0);
+    }
+    line
+}
+
+fn ask_for_response(guess: &str) -> Result<Response, Cow<'static, str>> {
+    let line = read_line("Colors: ");
+    let command = line.trim().to_ascii_lowercase();
+    let mut words = command.split_whitespace();
+    match (words.next(), words.next(), words.next()) {
+        (Some("undo"), None, None) => Ok(Response::Undo),
+        (Some("restart"), None, None) => Ok(Response::Restart),
+        (Some("!"), None, None) => Ok(Response::Rejected),
+        (Some("edit"), Some(n), None) => n
+            .parse()
+            .map(Response::Edit)
+            .map_err(|_| format!("'{}' is not a guess number.", n).into()),
+        (Some("edit"), _, _) => Err("Use `edit N` to change the colors of guess number N.")?,
+        _ => parse_correctness(&line, guess).map(Response::Colors),
+    }
+}
+
+fn ask_for_correctness(guess: &str) -> Result<[roget::Correctness; 5], Cow<'static, str>> {
+    parse_correctness(&read_line("Colors: "), guess)
+}
+
+fn parse_correctness(
+    answer: &str,
+    guess: &str,
+) -> Result<[roget::Correctness; 5], Cow<'static, str>> {
+    let pattern = answer
+        .parse::<roget::Pattern>()
+        .map_err(|e| e.to_string())?;
+    if !roget::Correctness::is_achievable(guess, pattern.0) {
+        Err(format!(
+            "No word gives the colors {} for {}; please double-check them.",
+            pattern,
+            guess.to_uppercase()
+        ))?;
+    }
+    Ok(pattern.0)
+}
+
+/// Reads a list of answers to play, and checks that the solver could guess each of them.
+fn load_answers(path: &std::path::Path) -> Result<String, String> {
+    let answers = std::fs::read_to_string(path)
+        .map_err(|e| format!("could not read answers from {}: {}", path.display(), e))?
+        .to_ascii_lowercase();
+    let known: std::collections::HashSet<_> =
+        roget::DICTIONARY.iter().map(|&(word, _)| word).collect();
+    if let Some(unknown) = answers.split_whitespace().find(|a| !known.contains(a)) {
+        return Err(format!(
+            "the answer '{}' in {} is not in the dictionary",
+            unknown,
+            path.display()
+        ));
+    }
+    Ok(answers)
+}
+
+/// A guesser that remembers every guess made by the guesser it wraps.
+struct Recorder<G> {
+    inner: G,
+    guesses: Vec<Cow<'static, str>>,
+}
+
+impl<G: Guesser> Guesser for Recorder<G> {
+    fn new_game(&mut self) {
+        self.inner.new_game()
+    }
+    fn guess(&mut self, history: &[Guess]) -> Cow<'static, str> {
+        let guess = self.inner.guess(history);
+        self.guesses.push(guess.clone());
+        guess
+    }
+    fn finish(&mut self, answer: &str, history: &[Guess], outcome: roget::Outcome) {
+        self.inner.finish(answer, history, outcome)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Game {
+    answer: String,
+    /// The number of guesses needed, or `None` if the guesser never got it.
+    score: Option<usize>,
+    /// Every guess that was made, in order.
+    guesses: Vec<Cow<'static, str>>,
+}
+
+/// The outcome of playing a series of games with one kind of guesser.
+struct Benchmark {
+    games: Vec<Game>,
+    /// How long it took to play all the games.
+    elapsed: Duration,
+}
+
+impl Benchmark {
+    /// Plays a game for each of the whitespace-separated `answers`, one after the other with the
+    /// same `guesser`.
+    fn run<G>(mut guesser: G, answers: &str, max: Option<usize>) -> Self
+    where
+        G: Guesser,
+    {
+        let w = roget::Wordle::new();
+        let start = Instant::now();
+        let games = answers
+            .split_whitespace()
+            .take(max.unwrap_or(usize::MAX))
+            .map(|answer| {
+                let mut recorder = Recorder {
+                    inner: &mut guesser,
+                    guesses: Vec::new(),
+                };
+                let score = w.play(answer, &mut recorder);
+                Game {
+                    answer: answer.to_string(),
+                    score,
+                    guesses: recorder.guesses,
+                }
+            })
+            .collect();
+        Self {
+            games,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    fn scores(&self) -> impl Iterator<Item = usize> + '_ {
+        self.games.iter().filter_map(|g| g.score)
+    }
+
+    /// The number of games that were won with each score, indexed by score.
+    fn histogram(&self) -> Vec<usize> {
+        let mut histogram = Vec::new();
+        for s in self.scores() {
             if s >= histogram.len() {
-                histogram.extend(std::iter::repeat(0).take(s - histogram.len() + 1));
+                histogram.resize(s + 1, 0);
             }
             histogram[s] += 1;
-            // eprintln!("guessed '{}' in {}", answer, s);
+        }
+        histogram
+    }
+
+    fn failures(&self) -> impl Iterator<Item = &str> + '_ {
+        self.games
+            .iter()
+            .filter(|g| g.score.is_none())
+            .map(|g| &*g.answer)
+    }
+
+    /// The average score across the games that were won.
+    fn average(&self) -> f64 {
+        let (games, score) = self
+            .scores()
+            .fold((0, 0), |(games, score), s| (games + 1, score + s));
+        score as f64 / games as f64
+    }
+
+    /// The standard deviation of the score across the games that were won.
+    fn stddev(&self) -> f64 {
+        let average = self.average();
+        let (games, squares) = self.scores().fold((0, 0.0), |(games, squares), s| {
+            (games + 1, squares + (s as f64 - average).powi(2))
+        });
+        (squares / games as f64).sqrt()
+    }
+
+    /// The score that `p` percent of the won games did at least as well as.
+    fn percentile(&self, p: usize) -> Option<usize> {
+        let mut scores: Vec<_> = self.scores().collect();
+        scores.sort_unstable();
+        // Nearest-rank percentile.
+        let rank = (p * scores.len()).div_ceil(100);
+        scores.get(rank.max(1) - 1).copied()
+    }
+
+    /// The fraction of all games that were won within Wordle's six guesses.
+    fn win_rate(&self) -> f64 {
+        self.scores().filter(|&s| s <= 6).count() as f64 / self.games.len() as f64
+    }
+
+    fn write_json(&self, out: impl Write) -> serde_json::Result<()> {
+        #[derive(Serialize)]
+        struct Report<'a> {
+            games: &'a [Game],
+            histogram: BTreeMap<usize, usize>,
+            mean: f64,
+            stddev: f64,
+            percentiles: BTreeMap<usize, Option<usize>>,
+            win_rate: f64,
+            failures: Vec<&'a str>,
+        }
+
+        serde_json::to_writer_pretty(
+            out,
+            &Report {
+                games: &self.games,
+                histogram: self.histogram().into_iter().enumerate().skip(1).collect(),
+                mean: self.average(),
+                stddev: self.stddev(),
+                percentiles: [50, 90, 95, 99]
+                    .into_iter()
+                    .map(|p| (p, self.percentile(p)))
+                    .collect(),
+                win_rate: self.win_rate(),
+                failures: self.failures().collect(),
+            },
+        )
+    }
+
+    fn write_csv(&self, mut out: impl Write) -> std::io::Result<()> {
+        writeln!(out, "answer,score,guesses")?;
+        for game in &self.games {
+            let score = game.score.map(|s| s.to_string()).unwrap_or_default();
+            writeln!(out, "{},{},{}", game.answer, score, game.guesses.join(" "))?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "statistic,value")?;
+        for (score, count) in self.histogram().into_iter().enumerate().skip(1) {
+            writeln!(out, "score_{},{}", score, count)?;
+        }
+        writeln!(out, "mean,{:.4}", self.average())?;
+        writeln!(out, "stddev,{:.4}", self.stddev())?;
+        for p in [50, 90, 95, 99] {
+            let score = self
+                .percentile(p)
+                .map(|s| s.to_string())
+                .unwrap_or_default();
+            writeln!(out, "p{},{}", p, score)?;
+        }
+        writeln!(out, "win_rate,{:.4}", self.win_rate())?;
+        let failures: Vec<_> = self.failures().collect();
+        writeln!(out, "failures,{}", failures.join(" "))?;
+        Ok(())
+    }
+}
+
+/// The parts of the `--output json` results that are needed to compare two runs.
+#[derive(Deserialize)]
+struct SavedBenchmark {
+    games: Vec<Game>,
+}
+
+/// How the per-game scores changed from one benchmark run (A) to another (B).
+#[derive(Debug)]
+struct Comparison<'a> {
+    /// The answer, score in A, and score in B, for every answer that both runs got with different
+    /// scores.
+    changed: Vec<(&'a str, usize, usize)>,
+    /// Answers that A guessed, but B did not.
+    newly_failed: Vec<&'a str>,
+    /// Answers that B guessed, but A did not.
+    newly_solved: Vec<&'a str>,
+    /// The number of answers that both runs guessed.
+    paired: usize,
+    /// The average of (B's score - A's score) across the answers both runs guessed, and the
+    /// two-sided p-value for it being zero, from a paired t-test.
+    ///
+    /// This is `None` if fewer than two answers were paired, as there's no variance to go by then.
+    change: Option<(f64, f64)>,
+}
+
+impl<'a> Comparison<'a> {
+    fn new(a: &'a [Game], b: &'a [Game]) -> Self {
+        let b: HashMap<_, _> = b.iter().map(|g| (&*g.answer, g.score)).collect();
+        let mut changed = Vec::new();
+        let mut newly_failed = Vec::new();
+        let mut newly_solved = Vec::new();
+        let mut deltas = Vec::new();
+        for game in a {
+            let answer = &*game.answer;
+            // Answers that only one of the runs played can't be compared.
+            let Some(&b_score) = b.get(answer) else {
+                continue;
+            };
+            match (game.score, b_score) {
+                (Some(a_score), Some(b_score)) => {
+                    if a_score != b_score {
+                        changed.push((answer, a_score, b_score));
+                    }
+                    deltas.push(b_score as f64 - a_score as f64);
+                }
+                (Some(_), None) => newly_failed.push(answer),
+                (None, Some(_)) => newly_solved.push(answer),
+                (None, None) => {}
+            }
+        }
+        // Biggest changes first, and regressions before improvements of the same size.
+        changed.sort_by_key(|&(answer, a, b)| (std::cmp::Reverse(a.abs_diff(b)), b < a, answer));
+
+        let change = (deltas.len() >= 2).then(|| {
+            let n = deltas.len() as f64;
+            let mean_change = deltas.iter().sum::<f64>() / n;
+            let variance = deltas
+                .iter()
+                .map(|d| (d - mean_change).powi(2))
+                .sum::<f64>()
+                / (n - 1.0);
+            let p_value = if variance > 0.0 {
+                let t = mean_change / (variance / n).sqrt();
+                t_test(t, n - 1.0)
+            } else if mean_change == 0.0 {
+                1.0
+            } else {
+                0.0
+            };
+            (mean_change, p_value)
+        });
+
+        Self {
+            changed,
+            newly_failed,
+            newly_solved,
+            paired: deltas.len(),
+            change,
+        }
+    }
+}
+
+/// The two-sided p-value of the statistic `t` under Student's t-distribution with `df` degrees of
+/// freedom.
+fn t_test(t: f64, df: f64) -> f64 {
+    incomplete_beta(df / 2.0, 0.5, df / (df + t * t))
+}
+
+/// The regularized incomplete beta function I_x(a, b), evaluated with the continued fraction from
+/// Numerical Recipes (section 6.4).
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    // The continued fraction converges quickly only on this side of the mean, so use the symmetry
+    // I_x(a, b) = 1 - I_(1-x)(b, a) on the other.
+    if x > (a + 1.0) / (a + b + 2.0) {
+        return 1.0 - incomplete_beta(b, a, 1.0 - x);
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp() / a;
+
+    // Lentz's method.
+    const TINY: f64 = 1e-300;
+    let nonzero = |v: f64| if v.abs() < TINY { TINY } else { v };
+    let mut c = 1.0;
+    let mut d = 1.0 / nonzero(1.0 - (a + b) * x / (a + 1.0));
+    let mut f = d;
+    for m in 1..1000 {
+        let m = m as f64;
+        let even = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        let odd = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        for numerator in [even, odd] {
+            d = 1.0 / nonzero(1.0 + numerator * d);
+            c = nonzero(1.0 + numerator / c);
+            f *= c * d;
+        }
+        if (c * d - 1.0).abs() < 1e-12 {
+            break;
+        }
+    }
+    front * f
+}
+
+/// The natural logarithm of the gamma function for `x > 0`, using the Lanczos approximation with
+/// g = 7, which is accurate to about 1e-15.
+fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const C: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    let x = x - 1.0;
+    let sum = C[1..]
+        .iter()
+        .enumerate()
+        .fold(C[0], |acc, (i, c)| acc + c / (x + i as f64 + 1.0));
+    let t = x + G + 0.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Finds the largest family of `words` that are the same as `answer` except in one position.
+///
+/// The family is returned as a pattern with a `_` in the varying position (like `_ight`) along
+/// with all the family's members, including `answer` itself if it's one of the `words`.
+fn word_family<'a>(answer: &str, words: &[&'a str]) -> (String, Vec<&'a str>) {
+    (0..answer.len())
+        .map(|i| {
+            let members: Vec<_> = words
+                .iter()
+                .copied()
+                .filter(|a| {
+                    a.len() == answer.len()
+                        && a.bytes()
+                            .zip(answer.bytes())
+                            .enumerate()
+                            .all(|(j, (x, y))| i == j || x == y)
+                })
+                .collect();
+            let mut pattern = answer.to_string();
+            pattern.replace_range(i..=i, "_");
+            (pattern, members)
+        })
+        // Prefer the earliest position on ties so the result is deterministic.
+        .rev()
+        .max_by_key(|(_, members)| members.len())
+        .expect("words are never empty")
+}
+
+fn hardest<G>(guesser: G, answers: &str, max: Option<usize>, top: usize, min_score: usize)
+where
+    G: Guesser,
+{
+    let results = Benchmark::run(guesser, answers, max);
+    let mut games: Vec<_> = results.games.iter().collect();
+    // Failures first, then by descending score.
+    games.sort_by_key(|g| (g.score.map_or(0, |s| usize::MAX - s), &g.answer));
+
+    println!("hardest answers:");
+    for game in games.iter().take(top) {
+        let score = game
+            .score
+            .map_or_else(|| "-".to_string(), |s| s.to_string());
+        println!("{:>2} {}: {}", score, game.answer, game.guesses.join(" "));
+    }
+
+    // The solver has to tell apart every word it could guess, not just the answers.
+    let words: Vec<_> = roget::DICTIONARY.iter().map(|&(word, _)| word).collect();
+    let mut families: BTreeMap<String, (Vec<&str>, Vec<&Game>)> = BTreeMap::new();
+    for game in games
+        .into_iter()
+        .filter(|g| g.score.is_none_or(|s| s >= min_score))
+    {
+        let (pattern, members) = word_family(&game.answer, &words);
+        // A family of one is just a hard word on its own.
+        let pattern = if members.len() > 1 {
+            pattern
         } else {
-            eprintln!("failed to guess '{}'", answer);
+            String::from("other")
+        };
+        let family = families
+            .entry(pattern)
+            .or_insert_with(|| (members, Vec::new()));
+        family.1.push(game);
+    }
+    let mut families: Vec<_> = families.into_iter().collect();
+    families.sort_by_key(|(pattern, (members, hard))| {
+        (
+            pattern == "other",
+            std::cmp::Reverse(hard.len()),
+            std::cmp::Reverse(members.len()),
+        )
+    });
+
+    println!();
+    println!(
+        "answers that took {} or more guesses, by word family:",
+        min_score
+    );
+    for (pattern, (members, hard)) in families {
+        let hard: Vec<_> = hard
+            .into_iter()
+            .map(|g| match g.score {
+                Some(s) => format!("{}({})", g.answer, s),
+                None => format!("{}(-)", g.answer),
+            })
+            .collect();
+        if pattern == "other" {
+            println!("{} ({} hard): {}", pattern, hard.len(), hard.join(" "));
+        } else {
+            println!(
+                "{} ({} hard of {} words): {}",
+                pattern,
+                hard.len(),
+                members.len(),
+                hard.join(" ")
+            );
         }
     }
+}
+
+fn compare(a: &[Game], b: &[Game]) {
+    let c = Comparison::new(a, b);
+    let worse = c.changed.iter().filter(|&&(_, a, b)| b > a).count();
+    println!(
+        "{} answers guessed in both runs: {} better, {} worse, {} unchanged",
+        c.paired,
+        c.changed.len() - worse,
+        worse,
+        c.paired - c.changed.len()
+    );
+    match c.change {
+        Some((mean_change, p_value)) => println!(
+            "average score change: {:+.4} (paired t-test, p = {:.4})",
+            mean_change, p_value
+        ),
+        None => println!("average score change: not enough paired answers"),
+    }
+    if !c.newly_failed.is_empty() {
+        println!("newly failed: {}", c.newly_failed.join(" "));
+    }
+    if !c.newly_solved.is_empty() {
+        println!("newly solved: {}", c.newly_solved.join(" "));
+    }
+    for (answer, a, b) in c.changed {
+        println!("{} {} -> {} ({:+})", answer, a, b, b as isize - a as isize);
+    }
+}
+
+fn play<G>(guesser: G, answers: &str, max: Option<usize>, output: Option<Format>)
+where
+    G: Guesser,
+{
+    let results = Benchmark::run(guesser, answers, max);
+    for answer in results.failures() {
+        eprintln!("failed to guess '{}'", answer);
+    }
+    let histogram = results.histogram();
     let sum: usize = histogram.iter().sum();
     for (score, count) in histogram.into_iter().enumerate().skip(1) {
         let frac = count as f64 / sum as f64;
@@ -190,7 +1727,77 @@ where
             count
         );
     }
-    eprintln!("average score: {:.4}", score as f64 / games as f64);
+    eprintln!("average score: {:.4}", results.average());
+
+    let stdout = std::io::stdout();
+    let written = match output {
+        Some(Format::Json) => results
+            .write_json(stdout.lock())
+            .map_err(std::io::Error::from)
+            .and_then(|()| writeln!(stdout.lock())),
+        Some(Format::Csv) => results.write_csv(stdout.lock()),
+        None => Ok(()),
+    };
+    if let Err(e) = written {
+        eprintln!("could not write results: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn sweep(answers: &str, max: Option<usize>, output: Option<PathBuf>) -> std::io::Result<()> {
+    let mut out: Box<dyn Write> = match output {
+        Some(path) => Box::new(std::io::BufWriter::new(std::fs::File::create(path)?)),
+        None => Box::new(std::io::stdout()),
+    };
+    writeln!(
+        out,
+        "sigmoid,rank_by,cutoff,hard_mode,games,average,1,2,3,4,5,6,7+,failures,seconds"
+    )?;
+    // Otherwise the first combinations would also pay for filling the cache that later ones reuse.
+    Solver::default().fill_cache();
+    for &rank_by in Rank::value_variants() {
+        for sigmoid in [true, false] {
+            for cutoff in [true, false] {
+                for hard_mode in [true, false] {
+                    let mut options = Solver::builder();
+                    options.rank_by = rank_by.into();
+                    options.sigmoid = sigmoid;
+                    options.cutoff = cutoff;
+                    options.hard_mode = hard_mode;
+
+                    let results = Benchmark::run(options.build(), answers, max);
+                    let histogram = results.histogram();
+                    let count = |score: usize| histogram.get(score).copied().unwrap_or(0);
+                    write!(
+                        out,
+                        "{},{},{},{},{},{:.4}",
+                        sigmoid,
+                        rank_by
+                            .to_possible_value()
+                            .expect("no Rank variants are skipped")
+                            .get_name(),
+                        cutoff,
+                        hard_mode,
+                        results.games.len(),
+                        results.average(),
+                    )?;
+                    for score in 1..=6 {
+                        write!(out, ",{}", count(score))?;
+                    }
+                    writeln!(
+                        out,
+                        ",{},{},{:.3}",
+                        histogram.iter().skip(7).sum::<usize>(),
+                        results.failures().count(),
+                        results.elapsed.as_secs_f64()
+                    )?;
+                    // A full sweep takes a while, so make each row visible as soon as it's done.
+                    out.flush()?;
+                }
+            }
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -209,4 +1816,353 @@ mod tests {
             [4, 3, 4, 4, 3, 4, 4, 3, 4, 3, 4, 3, 3, 4, 3, 4, 4, 4, 3, 3]
         );
     }
+
+    #[test]
+    fn benchmark_summary() {
+        let results = crate::Benchmark::run(roget::Solver::default(), crate::GAMES, Some(20));
+        assert_eq!(results.games.len(), 20);
+        assert_eq!(results.histogram(), [0, 0, 0, 9, 11]);
+        assert_eq!(results.failures().count(), 0);
+        assert!((results.average() - 3.55).abs() < 1e-9);
+        assert_eq!(results.percentile(50), Some(4));
+        assert_eq!(results.percentile(40), Some(3));
+        assert_eq!(results.win_rate(), 1.0);
+
+        let mut csv = Vec::new();
+        results.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let (games, summary) = csv.split_once("\n\n").unwrap();
+        assert_eq!(games.lines().count(), 21);
+        assert!(games.starts_with("answer,score,guesses\ncigar,"));
+        assert_eq!(
+            summary.lines().collect::<Vec<_>>(),
+            [
+                "statistic,value",
+                "score_1,0",
+                "score_2,0",
+                "score_3,9",
+                "score_4,11",
+                "mean,3.5500",
+                "stddev,0.4975",
+                "p50,4",
+                "p90,4",
+                "p95,4",
+                "p99,4",
+                "win_rate,1.0000",
+                "failures,",
+            ]
+        );
+
+        let first = &results.games[0];
+        assert_eq!(first.answer, "cigar");
+        assert_eq!(first.score, Some(first.guesses.len()));
+        assert_eq!(first.guesses.first().map(|g| &**g), Some("tares"));
+        assert_eq!(first.guesses.last().map(|g| &**g), Some("cigar"));
+    }
+
+    #[test]
+    fn compare_runs() {
+        let game = |answer: &str, score| crate::Game {
+            answer: answer.to_string(),
+            score,
+            guesses: Vec::new(),
+        };
+        let a = [
+            game("cigar", Some(4)),
+            game("rebut", Some(3)),
+            game("sissy", Some(4)),
+            game("humph", Some(5)),
+            game("awake", None),
+        ];
+        let b = [
+            game("cigar", Some(3)),
+            game("rebut", Some(3)),
+            game("sissy", Some(6)),
+            game("humph", None),
+            game("awake", Some(4)),
+            game("blush", Some(2)),
+        ];
+        let c = crate::Comparison::new(&a, &b);
+        assert_eq!(c.changed, [("sissy", 4, 6), ("cigar", 4, 3)]);
+        assert_eq!(c.newly_failed, ["humph"]);
+        assert_eq!(c.newly_solved, ["awake"]);
+        assert_eq!(c.paired, 3);
+        let (mean_change, p_value) = c.change.unwrap();
+        assert!((mean_change - 1.0 / 3.0).abs() < 1e-9);
+        // With two degrees of freedom, the two-sided p-value is 1 - |t| / sqrt(2 + t^2).
+        let t = mean_change / (7.0f64 / 9.0).sqrt();
+        assert!((p_value - (1.0 - t / (2.0 + t * t).sqrt())).abs() < 1e-9);
+
+        let same = crate::Comparison::new(&a, &a);
+        assert!(same.changed.is_empty());
+        assert_eq!(same.change, Some((0.0, 1.0)));
+
+        // One game, or none, says nothing about whether the change is significant.
+        let one = crate::Comparison::new(&a[..1], &b[..1]);
+        assert_eq!(one.changed, [("cigar", 4, 3)]);
+        assert_eq!(one.paired, 1);
+        assert_eq!(one.change, None);
+        let none = crate::Comparison::new(&a[..1], &b[1..]);
+        assert_eq!(none.paired, 0);
+        assert_eq!(none.change, None);
+    }
+
+    #[test]
+    fn custom_answers() {
+        let path = std::env::temp_dir().join(format!("roget-answers-{}.txt", std::process::id()));
+        std::fs::write(&path, "CIGAR rebut\nsissy\n").unwrap();
+        let answers = super::load_answers(&path).unwrap();
+        let results = crate::Benchmark::run(roget::Solver::default(), &answers, None);
+        let played: Vec<_> = results.games.iter().map(|g| g.answer.as_str()).collect();
+        assert_eq!(played, ["cigar", "rebut", "sissy"]);
+        assert!(results.failures().next().is_none());
+
+        std::fs::write(&path, "cigar zzzzz").unwrap();
+        let e = super::load_answers(&path).unwrap_err();
+        assert!(e.contains("'zzzzz'"), "{}", e);
+        std::fs::remove_file(&path).unwrap();
+        assert!(super::load_answers(&path).is_err());
+    }
+
+    #[test]
+    fn word_family() {
+        let answers = [
+            "fight", "light", "might", "tight", "lifts", "sight", "lying",
+        ];
+        let (pattern, members) = crate::word_family("light", &answers);
+        assert_eq!(pattern, "_ight");
+        assert_eq!(members, ["fight", "light", "might", "tight", "sight"]);
+
+        let (pattern, members) = crate::word_family("lying", &answers);
+        assert_eq!(pattern, "_ying");
+        assert_eq!(members, ["lying"]);
+
+        // Families include every word the solver might guess, not just other answers.
+        let words: Vec<_> = roget::DICTIONARY.iter().map(|&(word, _)| word).collect();
+        let (pattern, members) = crate::word_family("goner", &words);
+        assert_eq!(pattern, "_oner");
+        assert!(members.contains(&"boner") && members.contains(&"toner"));
+    }
+
+    #[test]
+    fn letter_knowledge() {
+        use roget::Correctness;
+        use std::borrow::Cow;
+
+        let history = [
+            roget::Guess {
+                word: Cow::Borrowed("tares"),
+                mask: [
+                    Correctness::Wrong,
+                    Correctness::Misplaced,
+                    Correctness::Misplaced,
+                    Correctness::Wrong,
+                    Correctness::Wrong,
+                ],
+            },
+            roget::Guess {
+                word: Cow::Borrowed("radar"),
+                mask: [
+                    Correctness::Misplaced,
+                    Correctness::Wrong,
+                    Correctness::Wrong,
+                    Correctness::Correct,
+                    Correctness::Wrong,
+                ],
+            },
+        ];
+        let known = crate::letter_knowledge(&history);
+        let letter = |l: u8| known[usize::from(l - b'a')];
+        assert_eq!(letter(b'a'), Some(Correctness::Correct));
+        assert_eq!(letter(b'r'), Some(Correctness::Misplaced));
+        assert_eq!(letter(b't'), Some(Correctness::Wrong));
+        assert_eq!(letter(b'd'), Some(Correctness::Wrong));
+        assert_eq!(letter(b'z'), None);
+    }
+
+    #[test]
+    fn parse_guess() {
+        let guess = crate::parse_guess("TARES:🟩🟨⬛⬛⬛").unwrap();
+        assert_eq!(guess.word, "tares");
+        assert_eq!(guess.mask, "CMWWW".parse::<roget::Pattern>().unwrap().0);
+
+        assert!(crate::parse_guess("tares").is_err());
+        assert!(crate::parse_guess("tare:CMWWW").is_err());
+        assert!(crate::parse_guess("t4res:CMWWW").is_err());
+        assert!(crate::parse_guess("tares:CMWW").is_err());
+    }
+
+    #[test]
+    fn api() {
+        let options = roget::Solver::builder();
+
+        let (status, reply) = super::api(options, "/suggest?history=tares:WMMWW,drain%3AWMMMW");
+        assert_eq!(status, 200);
+        assert_eq!(reply["guess"], "rival");
+
+        let (status, reply) = super::api(options, "/suggest?top=2");
+        assert_eq!(status, 200);
+        assert_eq!(reply["guesses"].as_array().unwrap().len(), 2);
+
+        let (status, reply) = super::api(options, "/candidates?history=tares:WMMWW&n=3");
+        assert_eq!(status, 200);
+        assert_eq!(reply["remaining"], 245);
+        assert_eq!(reply["candidates"].as_array().unwrap().len(), 3);
+
+        let (status, reply) = super::api(options, "/score?history=tares:WMMWW&guess=TARES");
+        assert_eq!(status, 200);
+        assert_eq!(reply["guess"]["word"], "tares");
+        assert_eq!(reply["guess"]["patterns"], 1);
+
+        let (status, reply) = super::api(options, "/score?guess=zzzzz");
+        assert_eq!((status, &reply["ok"]), (400, &false.into()));
+
+        let (status, reply) = super::api(
+            options,
+            "/suggest?history=tares:WMMWW,drain:WMMMW,rival:WCWCW",
+        );
+        assert_eq!(status, 400);
+        assert_eq!(reply["step"], 2);
+
+        let (status, _) = super::api(options, "/candidates?history=tares:W");
+        assert_eq!(status, 400);
+
+        let (status, _) = super::api(options, "/guess");
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn find() {
+        let words = super::find("_r_a_", "e", "s").unwrap();
+        assert!(!words.is_empty());
+        assert!(words.windows(2).all(|w| w[0].1 >= w[1].1));
+        for &(word, _) in &words {
+            let b = word.as_bytes();
+            assert_eq!((b[1], b[3]), (b'r', b'a'), "{}", word);
+            assert!(word.contains('e') && !word.contains('s'), "{}", word);
+        }
+        assert!(words.iter().any(|&(word, _)| word == "break"));
+
+        assert!(super::find("?????", "ee", "")
+            .unwrap()
+            .iter()
+            .all(|(word, _)| word.matches('e').count() >= 2));
+        assert_eq!(super::find("CIGAR", "", "").unwrap()[0].0, "cigar");
+        assert!(super::find("_r_a", "", "").is_err());
+        assert!(super::find("_r_a_", "e", "e").is_err());
+        assert!(super::find("_r-a_", "", "").is_err());
+    }
+
+    #[test]
+    fn grep_pipeline() {
+        let history = super::parse_history(&["geese:WCCWW"]).unwrap();
+        let constraints = roget::Constraints::new(&history);
+        assert_eq!(
+            super::grep_pipeline(&constraints),
+            format!("grep -E '{}' | grep -vE '(e.*){{3}}'", constraints.regex())
+        );
+        let counts = constraints.letter_counts();
+        assert_eq!(counts.len(), 1);
+        assert_eq!(super::describe_count(counts[0]), "e: at most 2");
+    }
+
+    #[test]
+    fn known_history() {
+        assert!(super::parse_known_history(&["sassy:WCWMC"]).is_ok());
+        let e = super::parse_known_history(&["tares:WWWWW", "sassy:?C?MC"])
+            .err()
+            .unwrap();
+        assert!(e.contains("'sassy'"), "{}", e);
+    }
+
+    #[test]
+    fn check_finished_game() {
+        let game =
+            |words: &[&str]| -> Vec<String> { words.iter().map(|w| w.to_string()).collect() };
+        assert!(super::check_finished_game("cigar", &game(&["tares", "cigar"])).is_ok());
+        let e = super::check_finished_game("cigar", &game(&["tares", "drain"])).unwrap_err();
+        assert!(e.contains("never guessed"), "{}", e);
+        let e = super::check_finished_game("zzzzz", &game(&["tares", "zzzzz"])).unwrap_err();
+        assert!(e.contains("not in the dictionary"), "{}", e);
+        let e = super::check_finished_game("cigar", &game(&["tar", "cigar"])).unwrap_err();
+        assert!(e.contains("five-letter"), "{}", e);
+    }
+
+    #[test]
+    fn parse_grid() {
+        use roget::Correctness::{Correct as C, Misplaced as M, Wrong as W};
+
+        let shared = "Wordle 1,234 3/6\n\n⬛🟨🟨⬛⬛\n⬛🟩🟨⬛⬛\n🟩🟩🟩🟩🟩\n";
+        let rows = vec![[W, M, M, W, W], [W, C, M, W, W], [C; 5]];
+        assert_eq!(super::parse_grid(shared), Ok(rows.clone()));
+        assert_eq!(super::parse_grid("WMMWW,wcmww/CCCCC"), Ok(rows.clone()));
+        assert_eq!(super::parse_grid("WMMWW WCMWW\nCCCCC"), Ok(rows));
+        assert!(super::parse_grid("WMMWW,WCMW").is_err());
+    }
+
+    #[test]
+    fn percent_decode() {
+        assert_eq!(
+            super::percent_decode("tares%3A%F0%9F%9F%A9+x"),
+            "tares:🟩 x"
+        );
+        assert_eq!(super::percent_decode("100%"), "100%");
+        assert_eq!(super::percent_decode("%zz"), "%zz");
+    }
+
+    #[test]
+    fn protocol() {
+        let mut p = crate::Protocol::new(roget::Solver::builder());
+        let mut send = |line: &str| p.handle(line).map(|reply| reply.to_string());
+
+        assert_eq!(send(""), None);
+        assert_eq!(send("suggest").unwrap(), r#"{"guess":"tares","ok":true}"#);
+        assert_eq!(
+            send("observe tares WMMWW").unwrap(),
+            r#"{"ok":true,"remaining":245}"#
+        );
+        assert_eq!(
+            send("observe drain wmmmw").unwrap(),
+            r#"{"ok":true,"remaining":20}"#
+        );
+        assert_eq!(send("suggest").unwrap(), r#"{"guess":"rival","ok":true}"#);
+        let reply: serde_json::Value =
+            serde_json::from_str(&send("candidates 3").unwrap()).unwrap();
+        assert_eq!(reply["remaining"], 20);
+        assert_eq!(reply["candidates"].as_array().unwrap().len(), 3);
+        let reply: serde_json::Value = serde_json::from_str(&send("suggest 2").unwrap()).unwrap();
+        assert_eq!(reply["guesses"][0]["word"], "rival");
+
+        // A contradiction names the guess that caused it, and can be rewound.
+        let reply: serde_json::Value =
+            serde_json::from_str(&send("observe rival WCWCW").unwrap()).unwrap();
+        assert_eq!(reply["ok"], false);
+        assert_eq!(reply["step"], 2);
+        assert_eq!(send("reset 2").unwrap(), r#"{"observed":2,"ok":true}"#);
+        assert_eq!(send("suggest").unwrap(), r#"{"guess":"rival","ok":true}"#);
+
+        assert_eq!(send("new").unwrap(), r#"{"ok":true}"#);
+        assert_eq!(send("suggest").unwrap(), r#"{"guess":"tares","ok":true}"#);
+
+        for bad in [
+            "observe tares",
+            "observe tares QQQQQ",
+            "candidates x",
+            "frobnicate",
+        ] {
+            let reply: serde_json::Value = serde_json::from_str(&send(bad).unwrap()).unwrap();
+            assert_eq!(reply["ok"], false, "{}", bad);
+            assert!(reply["error"].is_string(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn t_test() {
+        assert!((crate::t_test(0.0, 5.0) - 1.0).abs() < 1e-9);
+        // With one degree of freedom, this is the Cauchy distribution.
+        assert!((crate::t_test(1.0, 1.0) - 0.5).abs() < 1e-9);
+        assert!((crate::t_test(-2.0, 10.0) - 0.073388).abs() < 1e-6);
+        // With many degrees of freedom, it approaches the normal distribution.
+        assert!((crate::t_test(1.959964, 1e6) - 0.05).abs() < 1e-5);
+    }
 }
@@ -0,0 +1,156 @@
+use crate::Correctness;
+use std::fmt;
+use std::str::FromStr;
+
+/// The colors of the five tiles that a guess got.
+///
+/// This mostly exists to parse the colors from user input, and accepts several notations:
+///
+///  - `C`/`M`/`W` for correct, misplaced, and wrong.
+///  - `G`/`Y`/`B` (or `X`) for green, yellow, and black/gray.
+///  - The 🟩/🟨/⬛ squares (or ⬜ with the light theme) from a shared Wordle result.
+///  - The 🟧/🟦 squares used in high contrast mode for correct and misplaced.
+///  - `?` or `_` for a tile whose color isn't known.
+///
+/// Letters are case-insensitive, whitespace is ignored, and notations can be mixed. When
+/// displayed, a pattern uses the `C`/`M`/`W` notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pattern(pub [Correctness; 5]);
+
+impl From<[Correctness; 5]> for Pattern {
+    fn from(mask: [Correctness; 5]) -> Self {
+        Self(mask)
+    }
+}
+
+impl From<Pattern> for [Correctness; 5] {
+    fn from(pattern: Pattern) -> Self {
+        pattern.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParsePatternError {
+    /// The input did not have exactly five colors in it.
+    WrongLength(usize),
+    /// The input contained a character that isn't a color in any known notation.
+    UnknownColor(char),
+}
+
+impl fmt::Display for ParsePatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength(n) => write!(f, "expected exactly 5 colors, but got {}", n),
+            Self::UnknownColor(c) => write!(
+                f,
+                "the color '{}' wasn't recognized: \
+                use C/M/W, G/Y/B, or 🟩/🟨/⬛, and ? if unknown",
+                c
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParsePatternError {}
+
+impl FromStr for Pattern {
+    type Err = ParsePatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let colors = s
+            .chars()
+            // Emoji squares are sometimes followed by a variation selector.
+            .filter(|&c| !c.is_whitespace() && c != '\u{FE0F}')
+            .map(|c| match c.to_ascii_uppercase() {
+                'C' | 'G' | '🟩' | '🟧' => Ok(Correctness::Correct),
+                'M' | 'Y' | '🟨' | '🟦' => Ok(Correctness::Misplaced),
+                'W' | 'B' | 'X' | '⬛' | '⬜' => Ok(Correctness::Wrong),
+                '?' | '_' => Ok(Correctness::Unknown),
+                _ => Err(ParsePatternError::UnknownColor(c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let n = colors.len();
+        colors
+            .try_into()
+            .map(Pattern)
+            .map_err(|_| ParsePatternError::WrongLength(n))
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0 {
+            f.write_str(match c {
+                Correctness::Correct => "C",
+                Correctness::Misplaced => "M",
+                Correctness::Wrong => "W",
+                Correctness::Unknown => "?",
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParsePatternError, Pattern};
+
+    #[test]
+    fn notations() {
+        let expected = Pattern(mask![C M W W C]);
+        for input in [
+            "CMWWC",
+            "cmwwc",
+            "c m w w c",
+            "GYBBG",
+            "gyxbg",
+            "🟩🟨⬛⬛🟩",
+            "🟩🟨⬜⬜🟩",
+            "🟧🟦⬛⬛🟧",
+            "🟩🟨⬛\u{FE0F}⬛\u{FE0F}🟩",
+            "  C🟨b x G\n",
+        ] {
+            assert_eq!(input.parse(), Ok(expected), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            "CMWW".parse::<Pattern>(),
+            Err(ParsePatternError::WrongLength(4))
+        );
+        assert_eq!(
+            "🟩🟩🟩🟩🟩🟩".parse::<Pattern>(),
+            Err(ParsePatternError::WrongLength(6))
+        );
+        assert_eq!(
+            "CMWQC".parse::<Pattern>(),
+            Err(ParsePatternError::UnknownColor('Q'))
+        );
+        // The character is reported the way it was typed.
+        assert_eq!(
+            "cmwqc".parse::<Pattern>(),
+            Err(ParsePatternError::UnknownColor('q'))
+        );
+        assert_eq!(
+            "🟩🟥⬛⬛🟩".parse::<Pattern>(),
+            Err(ParsePatternError::UnknownColor('🟥'))
+        );
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!("C?_WM".parse(), Ok(Pattern(mask![C U U W M])));
+        assert_eq!("🟩?⬛_🟨".parse(), Ok(Pattern(mask![C U W U M])));
+        assert_eq!(Pattern(mask![U C U U W]).to_string(), "?C??W");
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let pattern = Pattern(mask![W M C M W]);
+        assert_eq!(pattern.to_string(), "WMCMW");
+        assert_eq!(pattern.to_string().parse(), Ok(pattern));
+    }
+}
@@ -1,14 +1,34 @@
-use crate::{Correctness, Guess, Guesser, PackedCorrectness, DICTIONARY, MAX_MASK_ENUM};
+use crate::{
+    Correctness, Guess, Guesser, Outcome, PackedCorrectness, Pattern, DICTIONARY, MAX_MASK_ENUM,
+};
 use once_cell::sync::OnceCell;
 use once_cell::unsync::OnceCell as UnSyncOnceCell;
 use std::borrow::Cow;
 use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 
 /// The initial set of words without any smoothing
 static INITIAL_COUNTS: OnceCell<Vec<(&'static str, f64, usize)>> = OnceCell::new();
 /// The initial set of words after applying sigmoid smoothing.
 static INITIAL_SIGMOID: OnceCell<Vec<(&'static str, f64, usize)>> = OnceCell::new();
 
+/// The index of each word in `DICTIONARY`.
+static INDICES: OnceCell<HashMap<&'static str, usize>> = OnceCell::new();
+
+fn word_index(word: &str) -> Option<usize> {
+    INDICES
+        .get_or_init(|| {
+            DICTIONARY
+                .iter()
+                .enumerate()
+                .map(|(idx, &(word, _))| (word, idx))
+                .collect()
+        })
+        .get(word)
+        .copied()
+}
+
 /// A per-thread cache of cached `Correctness` for each word pair.
 ///
 /// We make this thread-local so that access to it is as cheap as we can get it.
@@ -21,10 +41,15 @@ thread_local! {
 }
 
 pub struct Solver {
-    remaining: Cow<'static, Vec<(&'static str, f64, usize)>>,
+    remaining: Cow<'static, [(&'static str, f64, usize)]>,
     entropy: Vec<f64>,
     options: Options,
-    last_guess_idx: Option<usize>,
+    /// The entries of the history `remaining` has already been narrowed down by.
+    observed: Vec<Guess<'static>>,
+    /// The entry of the history that left no words remaining, if any.
+    eliminated_by: Option<usize>,
+    /// Whether each word in `DICTIONARY` must not be guessed, or empty if none are banned.
+    banned: Vec<bool>,
 }
 
 impl Default for Solver {
@@ -236,7 +261,9 @@ impl Options {
         Solver {
             remaining: Cow::Borrowed(remaining),
             entropy: Vec::new(),
-            last_guess_idx: None,
+            observed: Vec::new(),
+            eliminated_by: None,
+            banned: Vec::new(),
 
             options: self,
         }
@@ -269,152 +296,521 @@ impl Solver {
 }
 
 impl Solver {
-    fn trim(&mut self, mut cmp: impl FnMut(&str, usize) -> bool) {
-        if matches!(self.remaining, Cow::Owned(_)) {
-            self.remaining
-                .to_mut()
-                .retain(|&(word, _, word_idx)| cmp(word, word_idx));
+    /// Returns the `k` best next guesses given `history`, best first.
+    ///
+    /// This considers the same words, and ranks them the same way, as [`Guesser::guess`] does, so
+    /// the first candidate is the word the solver would guess next. When that word is picked
+    /// without scoring the others, like the hard-coded first guess, it comes first regardless of
+    /// how it scores.
+    pub fn rank_candidates(
+        &mut self,
+        history: &[Guess],
+        k: usize,
+    ) -> Result<Vec<Candidate>, NoCandidates> {
+        self.observe(history)?;
+        let step = self.step(history.len());
+        let obvious = self.obvious_guess(history);
+        let mut candidates: Vec<_> = obvious
+            .map(|word| {
+                self.score(&step, word)
+                    .expect("guesses are in the dictionary")
+            })
+            .into_iter()
+            .collect();
+        let ranked = candidates.len();
+        self.consider(&step, |c| {
+            if Some(c.word) != obvious {
+                candidates.push(c);
+            }
+        });
+        // NOTE: This is a stable sort, so ties are broken the same way as in `guess`.
+        candidates[ranked..].sort_by(|a, b| b.goodness.total_cmp(&a.goodness));
+        candidates.truncate(k);
+        Ok(candidates
+            .into_iter()
+            .map(|c| self.count_patterns(c))
+            .collect())
+    }
+
+    /// Returns every word that could still be the answer given `history`, most likely first.
+    ///
+    /// Each word comes with the probability the solver assigns to it being the answer.
+    pub fn possible_answers(
+        &mut self,
+        history: &[Guess],
+    ) -> Result<Vec<(&'static str, f64)>, NoCandidates> {
+        self.observe(history)?;
+        let remaining_p: f64 = self.remaining.iter().map(|&(_, p, _)| p).sum();
+        // NOTE: `remaining` is in the same order as `DICTIONARY`, which is sorted by count, and
+        // the probabilities only ever increase with the count.
+        Ok(self
+            .remaining
+            .iter()
+            .map(|&(word, p, _)| (word, p / remaining_p))
+            .collect())
+    }
+
+    /// Forgets the game so far, so that the solver can play a new one.
+    ///
+    /// This leaves the solver as it was when it was built, so words that were banned can be
+    /// guessed again.
+    pub fn reset(&mut self) {
+        self.banned.clear();
+        self.restart();
+    }
+
+    /// Forgets the history observed so far, but not which words are banned.
+    fn restart(&mut self) {
+        self.remaining = Cow::Borrowed(self.initial());
+        self.entropy.clear();
+        self.observed.clear();
+        self.eliminated_by = None;
+        if !self.banned.is_empty() {
+            let banned = std::mem::take(&mut self.banned);
+            self.trim(|_, word_idx| !banned[word_idx]);
+            self.banned = banned;
+        }
+    }
+
+    /// Works out the colors of every pair of words in [`DICTIONARY`] up front, rather than as
+    /// they're needed.
+    ///
+    /// This makes how long games take to play comparable across solvers, since none of them then
+    /// pay for filling the cache. Does nothing if the cache is disabled.
+    pub fn fill_cache(&self) {
+        if !self.options.cache {
+            return;
+        }
+        COMPUTES.with(|c| {
+            let c = c.get().unwrap();
+            for (row, &(guess, _)) in c.iter().zip(DICTIONARY.iter()) {
+                for (answer_idx, &(answer, _)) in DICTIONARY.iter().enumerate() {
+                    get_packed(row, guess, answer, answer_idx);
+                }
+            }
+        });
+    }
+
+    /// Works out which of the remaining words could be the answer behind some shared games.
+    ///
+    /// Each grid is the colors of every guess of one game, without the words that were guessed.
+    /// A word could be the answer if, for every row of every grid, there is some word in
+    /// [`DICTIONARY`] that gets those colors when guessed. Like with
+    /// [`possible_answers`](Self::possible_answers), the words come with the probability that
+    /// each one is the answer, and the most likely comes first.
+    ///
+    /// Rows can have [`Correctness::Unknown`] tiles, which any color fits.
+    pub fn reverse_solve<G: AsRef<[[Correctness; 5]]>>(
+        &self,
+        grids: &[G],
+    ) -> Vec<(&'static str, f64)> {
+        // For each distinct row, the masks that fit it. Only one does unless it has unknown tiles.
+        let mut rows: Vec<[bool; MAX_MASK_ENUM]> = Vec::new();
+        for &row in grids.iter().flat_map(|grid| grid.as_ref()) {
+            let mut fits = [false; MAX_MASK_ENUM];
+            for mask in all_masks() {
+                if mask
+                    .into_iter()
+                    .zip(row)
+                    .all(|(c, r)| r == Correctness::Unknown || c == r)
+                {
+                    fits[usize::from(u8::from(PackedCorrectness::from(mask)))] = true;
+                }
+            }
+            if !rows.contains(&fits) {
+                rows.push(fits);
+            }
+        }
+
+        let fits = |mask_of: &mut dyn FnMut(usize, &'static str) -> PackedCorrectness| {
+            let mut seen = [false; MAX_MASK_ENUM];
+            let mut satisfied = vec![false; rows.len()];
+            let mut missing = rows.len();
+            for (word_idx, &(word, _)) in DICTIONARY.iter().enumerate() {
+                if missing == 0 {
+                    break;
+                }
+                let idx = usize::from(u8::from(mask_of(word_idx, word)));
+                if seen[idx] {
+                    continue;
+                }
+                seen[idx] = true;
+                for (row, satisfied) in rows.iter().zip(&mut satisfied) {
+                    if row[idx] && !*satisfied {
+                        *satisfied = true;
+                        missing -= 1;
+                    }
+                }
+            }
+            missing == 0
+        };
+        let possible: Vec<_> = self
+            .remaining
+            .iter()
+            .filter(|&&(answer, _, answer_idx)| {
+                if self.options.cache {
+                    COMPUTES.with(|c| {
+                        let c = c.get().unwrap();
+                        fits(&mut |word_idx, word| {
+                            get_packed(&c[word_idx], word, answer, answer_idx)
+                        })
+                    })
+                } else {
+                    fits(&mut |_, word| PackedCorrectness::from(Correctness::compute(answer, word)))
+                }
+            })
+            .collect();
+
+        let possible_p: f64 = possible.iter().map(|&&(_, p, _)| p).sum();
+        possible
+            .into_iter()
+            .map(|&(word, p, _)| (word, p / possible_p))
+            .collect()
+    }
+
+    /// Never guess `word` again, and no longer consider it a possible answer.
+    ///
+    /// This is for when the game doesn't accept a word that's in [`DICTIONARY`]. Returns false,
+    /// and does not ban the word, if it's the only possible answer left.
+    pub fn ban(&mut self, word: &str) -> bool {
+        let Some(idx) = word_index(word) else {
+            // We'd never guess a word that isn't in the dictionary anyway.
+            return true;
+        };
+        if self.remaining.len() == 1 && self.remaining[0].2 == idx {
+            return false;
+        }
+        if self.banned.is_empty() {
+            self.banned = vec![false; DICTIONARY.len()];
+        }
+        self.banned[idx] = true;
+        self.trim(|_, word_idx| word_idx != idx);
+        true
+    }
+
+    /// Like [`Guesser::guess`], but returns an error instead of panicking if no word fits
+    /// `history`.
+    ///
+    /// That can't happen when the solver plays a [`Wordle`](crate::Wordle), but it can when the
+    /// history comes from a person, who may well have entered the wrong colors somewhere.
+    pub fn try_guess(&mut self, history: &[Guess]) -> Result<&'static str, NoCandidates> {
+        self.observe(history)?;
+        if let Some(word) = self.obvious_guess(history) {
+            return Ok(word);
+        }
+
+        let step = self.step(history.len());
+        self.entropy.push(step.remaining_entropy);
+        Ok(self.best_guess(&step).word)
+    }
+
+    /// Returns the next guess if it can be picked without scoring the candidates.
+    fn obvious_guess(&self, history: &[Guess]) -> Option<&'static str> {
+        if history.is_empty() && !self.is_banned(word_index("tares").unwrap()) {
+            // NOTE: I did a manual run with this commented out and it indeed produced "tares" as
+            // the first guess. It slows down the run by a lot though.
+            Some("tares")
+        } else if self.options.rank_by == Rank::First || self.remaining.len() == 1 {
+            Some(self.remaining.first().unwrap().0)
         } else {
-            self.remaining = Cow::Owned(
-                self.remaining
-                    .iter()
-                    .filter(|(word, _, word_idx)| cmp(word, *word_idx))
-                    .copied()
-                    .collect(),
-            );
+            None
         }
     }
-}
 
-impl Guesser for Solver {
-    fn guess(&mut self, history: &[Guess]) -> String {
-        let score = history.len() as f64;
+    fn best_guess(&self, step: &Step) -> Candidate {
+        let mut best: Option<Candidate> = None;
+        self.consider(step, |c| {
+            // Which one gives us a lower (expected) score?
+            if best.is_none_or(|best| c.goodness > best.goodness) {
+                best = Some(c);
+            }
+        });
+        let best = best.unwrap();
+        assert_ne!(best.goodness, 0.0);
+        best
+    }
 
-        if let Some(last) = history.last() {
-            if self.options.cache {
-                let reference = PackedCorrectness::from(last.mask);
+    /// Narrows down the remaining words using the entries of `history` that haven't been seen yet.
+    ///
+    /// If `history` doesn't pick up where the last one left off, the solver starts over from it.
+    fn observe(&mut self, history: &[Guess]) -> Result<(), NoCandidates> {
+        let extends = history.len() >= self.observed.len()
+            && self
+                .observed
+                .iter()
+                .zip(history)
+                .all(|(a, b)| a.mask == b.mask && a.word == b.word);
+        if !extends {
+            self.restart();
+        }
+        for (step, guess) in history.iter().enumerate().skip(self.observed.len()) {
+            let word_idx = word_index(&guess.word);
+            // Only words that aren't in the dictionary need a copy of their own.
+            self.observed.push(Guess {
+                word: match word_idx {
+                    Some(idx) => Cow::Borrowed(DICTIONARY[idx].0),
+                    None => Cow::Owned(guess.word.to_string()),
+                },
+                mask: guess.mask,
+            });
+            if self.eliminated_by.is_some() {
+                continue;
+            }
+            // The cache only holds complete masks, so unknown tiles have to go the slow way.
+            let guess_idx = word_idx
+                .filter(|_| self.options.cache && !guess.mask.contains(&Correctness::Unknown));
+            if let Some(guess_idx) = guess_idx {
+                let reference = PackedCorrectness::from(guess.mask);
                 COMPUTES.with(|c| {
-                    let row = &c.get().unwrap()[self.last_guess_idx.unwrap()];
+                    let row = &c.get().unwrap()[guess_idx];
                     self.trim(|word, word_idx| {
-                        reference == get_packed(row, &last.word, word, word_idx)
+                        reference == get_packed(row, &guess.word, word, word_idx)
                     });
                 });
             } else {
-                self.trim(|word, _| last.matches(word));
+                self.trim(|word, _| guess.matches(word));
+            }
+            if self.remaining.is_empty() {
+                self.eliminated_by = Some(step);
             }
         }
+        match self.eliminated_by {
+            Some(step) => Err(NoCandidates {
+                step,
+                word: history[step].word.to_string(),
+                mask: history[step].mask,
+            }),
+            None => Ok(()),
+        }
+    }
 
-        if history.is_empty() {
-            self.last_guess_idx = Some(
+    fn trim(&mut self, mut cmp: impl FnMut(&str, usize) -> bool) {
+        if matches!(self.remaining, Cow::Owned(_)) {
+            self.remaining
+                .to_mut()
+                .retain(|&(word, _, word_idx)| cmp(word, word_idx));
+        } else {
+            self.remaining = Cow::Owned(
                 self.remaining
                     .iter()
-                    .find(|(word, _, _)| &**word == "tares")
-                    .map(|&(_, _, idx)| idx)
-                    .unwrap(),
+                    .filter(|(word, _, word_idx)| cmp(word, *word_idx))
+                    .copied()
+                    .collect(),
             );
-            // NOTE: I did a manual run with this commented out and it indeed produced "tares" as
-            // the first guess. It slows down the run by a lot though.
-            return "tares".to_string();
-        } else if self.options.rank_by == Rank::First || self.remaining.len() == 1 {
-            let w = self.remaining.first().unwrap();
-            self.last_guess_idx = Some(w.2);
-            return w.0.to_string();
         }
-        assert!(!self.remaining.is_empty());
+    }
 
+    fn remaining_entropy(&self) -> f64 {
         let remaining_p: f64 = self.remaining.iter().map(|&(_, p, _)| p).sum();
-        let remaining_entropy = -self
+        -self
             .remaining
             .iter()
             .map(|&(_, p, _)| {
                 let p = p / remaining_p;
                 p * p.log2()
             })
-            .sum::<f64>();
-        self.entropy.push(remaining_entropy);
+            .sum::<f64>()
+    }
 
-        let mut best: Option<Candidate> = None;
-        let mut i = 0;
-        let stop = (self.remaining.len() / 3).max(20).min(self.remaining.len());
-        let consider = if self.options.hard_mode {
-            &*self.remaining
-        } else if self.options.sigmoid {
+    /// The words that are worth guessing next, in the order the solver considers them.
+    fn guessable(&self) -> &[(&'static str, f64, usize)] {
+        if self.options.hard_mode {
+            &self.remaining
+        } else {
+            self.initial()
+        }
+    }
+
+    /// Every word in [`DICTIONARY`], along with its probability before any guesses are made.
+    fn initial(&self) -> &'static [(&'static str, f64, usize)] {
+        if self.options.sigmoid {
             INITIAL_SIGMOID.get().unwrap()
         } else {
             INITIAL_COUNTS.get().unwrap()
+        }
+    }
+
+    /// Scores guessing `word` next given `history`, the same way the solver scores its own guesses.
+    ///
+    /// The word doesn't have to be one the solver would consider, and may even be known to be
+    /// wrong. Returns `None` if `word` is not in [`DICTIONARY`].
+    pub fn score_guess(
+        &mut self,
+        history: &[Guess],
+        word: &str,
+    ) -> Result<Option<Candidate>, NoCandidates> {
+        self.observe(history)?;
+        Ok(self.score(&self.step(history.len()), word))
+    }
+
+    /// Grades guessing `word` next given `history` against every other guess the solver could make.
+    ///
+    /// Returns `None` if `word` is not in [`DICTIONARY`].
+    pub fn evaluate_guess(
+        &mut self,
+        history: &[Guess],
+        word: &str,
+    ) -> Result<Option<Evaluation>, NoCandidates> {
+        self.observe(history)?;
+        let step = self.step(history.len());
+        let Some(guess) = self.score(&step, word) else {
+            return Ok(None);
         };
-        for &(word, count, word_idx) in consider {
-            // considering a world where we _did_ guess `word` and got `pattern` as the
-            // correctness. now, compute what _then_ is left.
-
-            // Rather than iterate over the patterns sequentially and add up the counts of words
-            // that result in that pattern, we can instead keep a running total for each pattern
-            // simultaneously by storing them in an array. We can do this since each candidate-word
-            // pair deterministically produces only one mask.
-            let mut totals = [0.0f64; MAX_MASK_ENUM];
-
-            let mut in_remaining = false;
-            if self.options.cache {
-                COMPUTES.with(|c| {
-                    let row = &c.get().unwrap()[word_idx];
-                    for (candidate, count, candidate_idx) in &*self.remaining {
-                        in_remaining |= word_idx == *candidate_idx;
-                        let idx = get_packed(row, word, candidate, *candidate_idx);
-                        totals[usize::from(u8::from(idx))] += count;
-                    }
-                });
-            } else {
-                for (candidate, count, candidate_idx) in &*self.remaining {
-                    in_remaining |= word_idx == *candidate_idx;
-                    let idx = PackedCorrectness::from(Correctness::compute(candidate, word));
-                    totals[usize::from(u8::from(idx))] += count;
-                }
+
+        let best = match self.obvious_guess(history) {
+            Some(word) => self.score(&step, word).unwrap(),
+            None => self.count_patterns(self.best_guess(&step)),
+        };
+
+        // Unlike when picking a guess, every valid guess counts here, not just the first few.
+        let mut rank = 1;
+        let mut out_of = 0;
+        for (n, &(other, count, other_idx)) in self.guessable().iter().enumerate() {
+            if self.is_banned(other_idx) {
+                continue;
             }
+            out_of += 1;
+            if other == guess.word {
+                continue;
+            }
+            let (other, _) = self.evaluate(&step, other, count, other_idx, n);
+            if other.goodness > guess.goodness {
+                rank += 1;
+            }
+        }
 
-            let sum: f64 = totals
-                .into_iter()
-                .filter(|t| *t != 0.0)
-                .map(|p| {
-                    let p_of_this_pattern = p as f64 / remaining_p as f64;
-                    p_of_this_pattern * p_of_this_pattern.log2()
-                })
-                .sum();
-
-            let p_word = if in_remaining {
-                count as f64 / remaining_p as f64
-            } else {
-                // TODO: penalize further.
-                0.0
+        let mut buckets: Vec<_> = self
+            .buckets(guess.word)
+            .into_iter()
+            .map(|(mask, (n, _))| (mask, n))
+            .collect();
+        buckets.sort_unstable_by(|(a_mask, a), (b_mask, b)| b.cmp(a).then(a_mask.cmp(b_mask)));
+
+        Ok(Some(Evaluation {
+            guess,
+            e_guesses_left: guess.e_score - step.score,
+            buckets,
+            rank,
+            out_of,
+            best,
+        }))
+    }
+
+    /// Splits a finished game into how skillful and how lucky each guess was.
+    ///
+    /// The colors of each guess are worked out from the `answer`, and the game stops at the guess
+    /// that got it right.
+    pub fn luck_and_skill<S: AsRef<str>>(
+        &mut self,
+        answer: &str,
+        guesses: &[S],
+    ) -> Result<Vec<LuckAndSkill>, NoCandidates> {
+        let mut history = Vec::new();
+        let mut report = Vec::new();
+        for word in guesses {
+            let word = word.as_ref();
+            self.observe(&history)?;
+            let best = match self.obvious_guess(&history) {
+                Some(best) => best,
+                None => self.best_guess(&self.step(history.len())).word,
             };
-            let e_info = -sum;
-            let goodness = match self.options.rank_by {
-                Rank::First => unreachable!("early return above"),
-                Rank::ExpectedScore => {
-                    // NOTE: Higher is better, so we negate the result.
-                    -(p_word * (score + 1.0)
-                        + (1.0 - p_word) * (score + est_steps_left(remaining_entropy - e_info)))
-                }
-                Rank::WeightedInformation => p_word * e_info,
-                Rank::InfoPlusProbability => p_word + e_info,
-                Rank::ExpectedInformation => e_info,
+
+            let remaining_p: f64 = self.remaining.iter().map(|&(_, p, _)| p).sum();
+            let e_info = |buckets: &BTreeMap<_, (usize, f64)>| -> f64 {
+                0.0 - buckets
+                    .values()
+                    .map(|&(_, p)| {
+                        let p = p / remaining_p;
+                        p * p.log2()
+                    })
+                    .sum::<f64>()
             };
-            if let Some(c) = best {
-                // Which one gives us a lower (expected) score?
-                if goodness > c.goodness {
-                    best = Some(Candidate {
-                        word,
-                        goodness,
-                        idx: word_idx,
-                    });
-                }
-            } else {
-                best = Some(Candidate {
-                    word,
-                    goodness,
-                    idx: word_idx,
-                });
+            let buckets = self.buckets(word);
+            let mask = Correctness::compute(answer, word);
+            let p_mask = buckets.get(&mask).map_or(0.0, |&(_, p)| p) / remaining_p;
+            report.push(LuckAndSkill {
+                word: word.to_string(),
+                mask,
+                e_info: e_info(&buckets),
+                info: 0.0 - p_mask.log2(),
+                best,
+                best_e_info: e_info(&self.buckets(best)),
+            });
+
+            history.push(Guess {
+                word: Cow::Owned(word.to_string()),
+                mask,
+            });
+            if mask == [Correctness::Correct; 5] {
+                break;
+            }
+        }
+        // Make sure the last colors fit some word too.
+        self.observe(&history)?;
+        Ok(report)
+    }
+
+    /// Splits the remaining words by the colors guessing `word` would get them.
+    ///
+    /// For each set of colors, this gives the number of words that would get them, and the sum of
+    /// their probabilities.
+    fn buckets(&self, word: &str) -> BTreeMap<[Correctness; 5], (usize, f64)> {
+        let mut buckets = BTreeMap::new();
+        for &(answer, p, _) in &*self.remaining {
+            let bucket = buckets
+                .entry(Correctness::compute(answer, word))
+                .or_insert((0, 0.0));
+            bucket.0 += 1;
+            bucket.1 += p;
+        }
+        buckets
+    }
+
+    /// Scores guessing any word in [`DICTIONARY`] next.
+    fn score(&self, step: &Step, word: &str) -> Option<Candidate> {
+        let word_idx = word_index(word)?;
+        let (word, count, _) = self.initial()[word_idx];
+        let (candidate, _) = self.evaluate(step, word, count, word_idx, word_idx);
+        Some(self.count_patterns(candidate))
+    }
+
+    /// Fills in how guessing the candidate's word would split up the remaining words.
+    ///
+    /// Picking a guess doesn't need this, so [`evaluate`](Self::evaluate) leaves it out.
+    fn count_patterns(&self, mut candidate: Candidate) -> Candidate {
+        let buckets = self.buckets(candidate.word);
+        candidate.patterns = buckets.len();
+        candidate.largest_pattern = buckets.values().map(|&(n, _)| n).max().unwrap_or(0);
+        candidate
+    }
+
+    /// Whether the word at `word_idx` in [`DICTIONARY`] must not be guessed.
+    fn is_banned(&self, word_idx: usize) -> bool {
+        self.banned.get(word_idx).copied().unwrap_or(false)
+    }
+
+    fn step(&self, guesses: usize) -> Step {
+        Step {
+            score: guesses as f64,
+            remaining_entropy: self.remaining_entropy(),
+            remaining_p: self.remaining.iter().map(|&(_, p, _)| p).sum(),
+        }
+    }
+
+    /// Scores every word worth guessing next, in the order the solver considers them.
+    fn consider(&self, step: &Step, mut visit: impl FnMut(Candidate)) {
+        let mut i = 0;
+        let stop = (self.remaining.len() / 3).max(20).min(self.remaining.len());
+        for (n, &(word, count, word_idx)) in self.guessable().iter().enumerate() {
+            if self.is_banned(word_idx) {
+                // Only possible when we're not just considering `remaining`, which has already
+                // been trimmed.
+                continue;
             }
 
+            let (candidate, in_remaining) = self.evaluate(step, word, count, word_idx, n);
+            visit(candidate);
+
             if self.options.cutoff && in_remaining {
                 i += 1;
                 if i >= stop {
@@ -422,13 +818,119 @@ impl Guesser for Solver {
                 }
             }
         }
-        let best = best.unwrap();
-        assert_ne!(best.goodness, 0.0);
-        self.last_guess_idx = Some(best.idx);
-        best.word.to_string()
     }
 
-    fn finish(&self, guesses: usize) {
+    /// Scores guessing `word`, and returns whether it could be the answer.
+    ///
+    /// `count` is the word's (possibly smoothed) count, and `n` is its position among the words
+    /// being considered.
+    fn evaluate(
+        &self,
+        step: &Step,
+        word: &'static str,
+        count: f64,
+        word_idx: usize,
+        n: usize,
+    ) -> (Candidate, bool) {
+        let &Step {
+            score,
+            remaining_entropy,
+            remaining_p,
+        } = step;
+
+        // considering a world where we _did_ guess `word` and got `pattern` as the
+        // correctness. now, compute what _then_ is left.
+
+        // Rather than iterate over the patterns sequentially and add up the counts of words
+        // that result in that pattern, we can instead keep a running total for each pattern
+        // simultaneously by storing them in an array. We can do this since each candidate-word
+        // pair deterministically produces only one mask.
+        let mut totals = [0.0f64; MAX_MASK_ENUM];
+
+        let mut in_remaining = false;
+        if self.options.cache {
+            COMPUTES.with(|c| {
+                let row = &c.get().unwrap()[word_idx];
+                for (candidate, count, candidate_idx) in &*self.remaining {
+                    in_remaining |= word_idx == *candidate_idx;
+                    let idx =
+                        usize::from(u8::from(get_packed(row, word, candidate, *candidate_idx)));
+                    totals[idx] += count;
+                }
+            });
+        } else {
+            for (candidate, count, candidate_idx) in &*self.remaining {
+                in_remaining |= word_idx == *candidate_idx;
+                let idx = PackedCorrectness::from(Correctness::compute(candidate, word));
+                let idx = usize::from(u8::from(idx));
+                totals[idx] += count;
+            }
+        }
+
+        let sum: f64 = totals
+            .into_iter()
+            .filter(|t| *t != 0.0)
+            .map(|p| {
+                let p_of_this_pattern = p / remaining_p;
+                p_of_this_pattern * p_of_this_pattern.log2()
+            })
+            .sum();
+
+        let p_word = if in_remaining {
+            count / remaining_p
+        } else {
+            // TODO: penalize further.
+            0.0
+        };
+        // NOTE: Subtracting from 0.0 rather than negating avoids a confusing -0 bits.
+        let e_info = 0.0 - sum;
+        let e_score = p_word * (score + 1.0)
+            + (1.0 - p_word) * (score + est_steps_left(remaining_entropy - e_info));
+        let goodness = match self.options.rank_by {
+            // Keep the order the words are considered in.
+            Rank::First => -(n as f64),
+            // NOTE: Higher is better, so we negate the result.
+            Rank::ExpectedScore => -e_score,
+            Rank::WeightedInformation => p_word * e_info,
+            Rank::InfoPlusProbability => p_word + e_info,
+            Rank::ExpectedInformation => e_info,
+        };
+        let candidate = Candidate {
+            word,
+            p_word,
+            e_info,
+            e_score,
+            // These don't matter for the ranking, so they're only filled in by `count_patterns`.
+            patterns: 0,
+            largest_pattern: 0,
+            goodness,
+        };
+        (candidate, in_remaining)
+    }
+}
+
+/// What the solver knows at the point where it's choosing its next guess.
+struct Step {
+    /// The number of guesses made so far.
+    score: f64,
+    remaining_entropy: f64,
+    /// The sum of the probabilities of the remaining words.
+    remaining_p: f64,
+}
+
+impl Guesser for Solver {
+    fn new_game(&mut self) {
+        self.reset();
+    }
+
+    fn guess(&mut self, history: &[Guess]) -> Cow<'static, str> {
+        Cow::Borrowed(self.try_guess(history).unwrap_or_else(|e| panic!("{}", e)))
+    }
+
+    fn finish(&mut self, _answer: &str, _history: &[Guess], outcome: Outcome) {
+        let Outcome::Won(guesses) = outcome else {
+            return;
+        };
         if PRINT_ESTIMATION {
             for (i, &entropy) in self.entropy.iter().enumerate() {
                 // i == 0 is the entropy that was left _after_ guessing the first word.
@@ -442,9 +944,574 @@ impl Guesser for Solver {
     }
 }
 
+/// The error returned when no word in the dictionary fits a history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NoCandidates {
+    /// The index in the history of the guess that ruled out the last remaining words.
+    pub step: usize,
+
+    /// The word that was guessed in that step.
+    pub word: String,
+
+    /// The colors that word got.
+    pub mask: [Correctness; 5],
+}
+
+impl fmt::Display for NoCandidates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no word fits the colors {} for guess #{} ({})",
+            Pattern(self.mask),
+            self.step + 1,
+            self.word.to_uppercase()
+        )
+    }
+}
+
+impl std::error::Error for NoCandidates {}
+
+/// A word the solver considered guessing next, and how it scored.
 #[derive(Debug, Copy, Clone)]
-struct Candidate {
-    word: &'static str,
-    goodness: f64,
-    idx: usize,
+#[non_exhaustive]
+pub struct Candidate {
+    pub word: &'static str,
+
+    /// The probability that this word is the answer.
+    pub p_word: f64,
+
+    /// The expected information, in bits, gained from guessing this word.
+    pub e_info: f64,
+
+    /// The expected total number of guesses needed to win if this word is guessed next.
+    pub e_score: f64,
+
+    /// The number of distinct patterns that guessing this word could produce.
+    pub patterns: usize,
+
+    /// The number of remaining words that all produce the most common pattern for this guess.
+    pub largest_pattern: usize,
+
+    /// How good the solver thinks this guess is according to [`Options::rank_by`].
+    ///
+    /// Higher is better. The scale depends on the ranking used.
+    pub goodness: f64,
+}
+
+/// How a guess compares to the other guesses that could have been made instead.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Evaluation {
+    /// How the guess scored.
+    pub guess: Candidate,
+
+    /// The expected number of guesses, including this one, that are still needed to win.
+    pub e_guesses_left: f64,
+
+    /// The colors the guess could get, and how many of the remaining words would give each one.
+    ///
+    /// The most common colors come first.
+    pub buckets: Vec<([Correctness; 5], usize)>,
+
+    /// Where the guess ranks among all valid guesses, starting at 1 for the best one.
+    ///
+    /// Guesses that score the same share a rank.
+    pub rank: usize,
+
+    /// The number of valid guesses there were.
+    ///
+    /// This doesn't include the guess itself if it wasn't valid, like in hard mode when it can't
+    /// be the answer. Such a guess can then rank below all the valid ones.
+    pub out_of: usize,
+
+    /// The guess the solver would have made.
+    pub best: Candidate,
+}
+
+impl Evaluation {
+    /// The bits of information lost by not making the solver's guess.
+    pub fn info_gap(&self) -> f64 {
+        self.best.e_info - self.guess.e_info
+    }
+
+    /// The extra guesses the guess is expected to take compared to the solver's guess.
+    pub fn score_gap(&self) -> f64 {
+        self.guess.e_score - self.best.e_score
+    }
+}
+
+/// Every mask that a guess can get, along with some that it can't, like four greens and a yellow.
+fn all_masks() -> impl Iterator<Item = [Correctness; 5]> {
+    const COLORS: [Correctness; 3] = [
+        Correctness::Correct,
+        Correctness::Misplaced,
+        Correctness::Wrong,
+    ];
+    (0..MAX_MASK_ENUM).map(|mut n| {
+        let mut mask = [Correctness::Wrong; 5];
+        for c in mask.iter_mut().rev() {
+            *c = COLORS[n % 3];
+            n /= 3;
+        }
+        mask
+    })
+}
+
+/// How skillful and how lucky one guess of a finished game was.
+///
+/// All amounts of information are in bits.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct LuckAndSkill {
+    /// The word that was guessed.
+    pub word: String,
+
+    /// The colors the guess got.
+    pub mask: [Correctness; 5],
+
+    /// The information the guess was expected to give.
+    pub e_info: f64,
+
+    /// The information the guess actually gave.
+    pub info: f64,
+
+    /// The word the solver would have guessed instead.
+    pub best: &'static str,
+
+    /// The information the solver's guess was expected to give.
+    pub best_e_info: f64,
+}
+
+impl LuckAndSkill {
+    /// How much more information the guess was expected to give than the solver's guess.
+    ///
+    /// This is usually negative, but can be positive when the solver doesn't pick its guesses by
+    /// information alone.
+    pub fn skill(&self) -> f64 {
+        self.e_info - self.best_e_info
+    }
+
+    /// How much more information the guess gave than it was expected to.
+    pub fn luck(&self) -> f64 {
+        self.info - self.e_info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{word_index, INITIAL_SIGMOID};
+    use crate::{Correctness, Guess, Guesser, Options, Rank, Solver};
+    use std::borrow::Cow;
+
+    #[test]
+    fn rank_candidates_agrees_with_guess() {
+        let history = [Guess {
+            word: Cow::Borrowed("tares"),
+            mask: mask![W M M W W],
+        }];
+        let mut solver = Solver::default();
+        let ranked = solver.rank_candidates(&history, 5).unwrap();
+        assert_eq!(ranked.len(), 5);
+        assert!(ranked.windows(2).all(|w| w[0].goodness >= w[1].goodness));
+        for c in &ranked {
+            assert!(c.patterns >= 1);
+            assert!(c.largest_pattern >= 1);
+            assert!(c.e_info > 0.0);
+            assert!((0.0..=1.0).contains(&c.p_word));
+        }
+        // Ranking shouldn't disturb the solver's own state.
+        assert_eq!(solver.guess(&history), ranked[0].word);
+    }
+
+    #[test]
+    fn rank_candidates_agrees_with_obvious_guess() {
+        let history: Vec<_> = [
+            ("tares", mask![W M M W W]),
+            ("drain", mask![W M M M W]),
+            ("rival", mask![M C W C W]),
+        ]
+        .into_iter()
+        .map(|(word, mask)| Guess {
+            word: Cow::Borrowed(word),
+            mask,
+        })
+        .collect();
+
+        // Just picking the first word, even when any word may be guessed.
+        let mut solver = Options {
+            rank_by: Rank::First,
+            hard_mode: false,
+            ..Default::default()
+        }
+        .build();
+        let ranked = solver.rank_candidates(&history, 2).unwrap();
+        assert_eq!(ranked.len(), 2);
+        assert_ne!(ranked[0].word, ranked[1].word);
+        assert_eq!(ranked[0].word, solver.try_guess(&history).unwrap());
+
+        // Only one word is left, which isn't the one that gives the most information.
+        let mut history = history;
+        history.push(Guess {
+            word: Cow::Borrowed("cimar"),
+            mask: mask![C C W C C],
+        });
+        let mut solver = Options {
+            rank_by: Rank::ExpectedInformation,
+            ..Default::default()
+        }
+        .build();
+        assert_eq!(solver.possible_answers(&history).unwrap().len(), 1);
+        let ranked = solver.rank_candidates(&history, 1).unwrap();
+        assert_eq!(ranked[0].word, solver.try_guess(&history).unwrap());
+    }
+
+    #[test]
+    fn contradiction() {
+        let history = [
+            Guess {
+                word: Cow::Borrowed("tares"),
+                mask: mask![W M M W W],
+            },
+            Guess {
+                word: Cow::Borrowed("drain"),
+                mask: mask![W M M M W],
+            },
+            // Only "cigar" fits, which would give M C W C W.
+            Guess {
+                word: Cow::Borrowed("rival"),
+                mask: mask![W C W C W],
+            },
+            Guess {
+                word: Cow::Borrowed("cigar"),
+                mask: mask![C C C C W],
+            },
+        ];
+        let mut solver = Solver::default();
+        assert_eq!(solver.try_guess(&history[..3]).unwrap_err().step, 2);
+        // Feedback given after the contradiction doesn't change which step caused it.
+        let e = solver.try_guess(&history).unwrap_err();
+        assert_eq!(e.step, 2);
+        assert_eq!(e.word, "rival");
+        assert_eq!(e.mask, mask![W C W C W]);
+        assert!(solver.rank_candidates(&history, 1).is_err());
+
+        let mut solver = Solver::default();
+        assert_eq!(solver.try_guess(&history[..2]).unwrap(), "rival");
+    }
+
+    #[test]
+    fn possible_answers() {
+        let history = [
+            Guess {
+                word: Cow::Borrowed("tares"),
+                mask: mask![W M M W W],
+            },
+            Guess {
+                word: Cow::Borrowed("drain"),
+                mask: mask![W M M M W],
+            },
+        ];
+        let mut solver = Solver::default();
+        let possible = solver.possible_answers(&history).unwrap();
+        assert!(possible.iter().any(|&(word, _)| word == "cigar"));
+        assert!(possible
+            .iter()
+            .all(|(word, _)| history.iter().all(|g| g.matches(word))));
+        assert!(possible.windows(2).all(|w| w[0].1 >= w[1].1));
+        let total: f64 = possible.iter().map(|&(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ban() {
+        let mut history = vec![
+            Guess {
+                word: Cow::Borrowed("tares"),
+                mask: mask![W M M W W],
+            },
+            Guess {
+                word: Cow::Borrowed("drain"),
+                mask: mask![W M M M W],
+            },
+        ];
+        let mut solver = Solver::default();
+        assert_eq!(solver.try_guess(&history).unwrap(), "rival");
+        assert!(solver.ban("rival"));
+        assert_ne!(solver.try_guess(&history).unwrap(), "rival");
+        assert!(solver
+            .possible_answers(&history)
+            .unwrap()
+            .iter()
+            .all(|&(word, _)| word != "rival"));
+
+        history.push(Guess {
+            word: Cow::Borrowed("rival"),
+            mask: Correctness::compute("cigar", "rival"),
+        });
+        let mut solver = Solver::default();
+        assert_eq!(solver.possible_answers(&history).unwrap().len(), 2);
+        assert!(solver.ban("cimar"));
+        assert!(!solver.ban("cigar"));
+        assert_eq!(solver.try_guess(&history).unwrap(), "cigar");
+    }
+
+    #[test]
+    fn score_guess() {
+        let history = [Guess {
+            word: Cow::Borrowed("tares"),
+            mask: mask![W M M W W],
+        }];
+        let mut solver = Solver::default();
+        let best = solver.rank_candidates(&history, 1).unwrap()[0];
+        let scored = solver.score_guess(&history, best.word).unwrap().unwrap();
+        assert_eq!(scored.goodness, best.goodness);
+        assert_eq!(scored.patterns, best.patterns);
+
+        // Words that are known to be wrong can still be scored.
+        let wrong = solver.score_guess(&history, "tares").unwrap().unwrap();
+        assert_eq!(wrong.p_word, 0.0);
+        assert_eq!(wrong.patterns, 1);
+        assert_eq!(wrong.e_info, 0.0);
+
+        assert!(solver.score_guess(&history, "zzzzz").unwrap().is_none());
+    }
+
+    #[test]
+    fn evaluate_guess() {
+        let history = vec![Guess {
+            word: Cow::Borrowed("tares"),
+            mask: mask![W M M W W],
+        }];
+        let mut solver = Solver::default();
+        let pick = solver.try_guess(&history).unwrap();
+
+        let best = solver.evaluate_guess(&history, pick).unwrap().unwrap();
+        assert_eq!(best.rank, 1);
+        assert_eq!(best.best.word, pick);
+        assert_eq!(best.score_gap(), 0.0);
+        assert_eq!(best.info_gap(), 0.0);
+        assert_eq!(best.buckets.iter().map(|&(_, n)| n).sum::<usize>(), 245);
+        assert_eq!(best.buckets.len(), best.guess.patterns);
+        assert_eq!(best.buckets[0].1, best.guess.largest_pattern);
+        assert!(best.buckets.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert!((best.e_guesses_left - (best.guess.e_score - 1.0)).abs() < 1e-9);
+
+        // Guessing the same word again tells us nothing.
+        let repeat = solver.evaluate_guess(&history, "tares").unwrap().unwrap();
+        assert_eq!(repeat.buckets, vec![(mask![W M M W W], 245)]);
+        assert!(repeat.rank > 1);
+        // In hard mode, it's not even a valid guess.
+        assert_eq!(repeat.out_of, 245);
+        assert!(repeat.rank <= repeat.out_of + 1);
+        assert!(repeat.score_gap() > 0.0);
+        assert!(repeat.info_gap() > 0.0);
+        assert_eq!(repeat.best.word, pick);
+
+        assert!(solver.evaluate_guess(&history, "zzzzz").unwrap().is_none());
+    }
+
+    #[test]
+    fn luck_and_skill() {
+        let mut solver = Solver::default();
+        let report = solver
+            .luck_and_skill("cigar", &["tares", "drain", "rival", "cigar", "cigar"])
+            .unwrap();
+        // The game ends as soon as the answer is guessed.
+        assert_eq!(report.len(), 4);
+        assert_eq!(report[0].mask, mask![W M M W W]);
+        assert_eq!(report[3].mask, mask![C C C C C]);
+        for step in &report {
+            // These are the guesses the solver makes itself.
+            assert_eq!(step.best, step.word);
+            assert!(step.skill().abs() < 1e-9);
+            assert!((step.info - step.e_info - step.luck()).abs() < 1e-9);
+        }
+
+        // Together, the guesses tell us exactly how unlikely the answer was to begin with.
+        let total: f64 = INITIAL_SIGMOID
+            .get()
+            .unwrap()
+            .iter()
+            .map(|&(_, p, _)| p)
+            .sum();
+        let cigar = INITIAL_SIGMOID.get().unwrap()[word_index("cigar").unwrap()].1;
+        let info: f64 = report.iter().map(|step| step.info).sum();
+        assert!((info - (total / cigar).log2()).abs() < 1e-9);
+
+        let report = Solver::default()
+            .luck_and_skill("cigar", &["crane", "cigar"])
+            .unwrap();
+        assert_eq!(report[0].best, "tares");
+        assert!(report[0].skill() < 0.0);
+
+        // No word fits the colors of guessing an answer roget doesn't know.
+        let unknown = Solver::default().luck_and_skill("zzzzz", &["tares", "zzzzz"]);
+        assert_eq!(unknown.unwrap_err().step, 1);
+    }
+
+    #[test]
+    fn reverse_solve() {
+        // Checking every word in the dictionary takes a while, so start from the ones that fit a
+        // guess we know of.
+        let history = [Guess {
+            word: Cow::Borrowed("tares"),
+            mask: mask![W M M W W],
+        }];
+        let mut solver = Solver::default();
+        solver.possible_answers(&history).unwrap();
+        let grid = |answer: &str, guesses: &[&str]| -> Vec<_> {
+            guesses
+                .iter()
+                .map(|guess| Correctness::compute(answer, guess))
+                .collect()
+        };
+        let grids = [
+            grid("cigar", &["tares", "drain", "rival", "cigar"]),
+            grid("cigar", &["crane", "cigar"]),
+            grid("cigar", &["pique", "mucky", "cigar"]),
+        ];
+        let possible = solver.reverse_solve(&grids);
+        assert!(possible.iter().any(|&(word, _)| word == "cigar"));
+        assert!(possible.len() < 245);
+        assert!((possible.iter().map(|&(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(possible.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        // Every row of a grid should be possible to get for each of the answers.
+        for &(answer, _) in &possible {
+            for mask in grids.iter().flatten() {
+                assert!(
+                    crate::DICTIONARY
+                        .iter()
+                        .any(|&(word, _)| Correctness::compute(answer, word) == *mask),
+                    "{} can't get {:?}",
+                    answer,
+                    mask
+                );
+            }
+        }
+
+        // Only one letter can be misplaced if the other four are all correct.
+        assert!(solver.reverse_solve(&[[mask![C C C C M]]]).is_empty());
+        assert_eq!(
+            solver.reverse_solve(&[[mask![C C C C U]]]),
+            solver.reverse_solve::<[_; 0]>(&[])
+        );
+        let partly = solver.reverse_solve(&[
+            grid("cigar", &["crane", "cigar"]),
+            vec![mask![U C W U U], mask![C C C C C]],
+        ]);
+        assert!(partly.iter().any(|&(word, _)| word == "cigar"));
+
+        let mut uncached = Options {
+            cache: false,
+            ..Solver::builder()
+        }
+        .build();
+        uncached.possible_answers(&history).unwrap();
+        assert_eq!(uncached.reverse_solve(&grids), possible);
+    }
+
+    #[test]
+    fn unknown_tiles() {
+        let guess = |mask| Guess {
+            word: Cow::Borrowed("tares"),
+            mask,
+        };
+        let possible = |options: Options, history: &[Guess]| {
+            let mut solver = options.build();
+            solver.possible_answers(history).unwrap()
+        };
+        let cached = Solver::builder();
+        let uncached = Options {
+            cache: false,
+            ..cached
+        };
+
+        // Knowing nothing rules nothing out.
+        let all = possible(cached, &[]);
+        assert_eq!(possible(cached, &[guess(mask![U U U U U])]), all);
+
+        let known = possible(cached, &[guess(mask![W M M W W])]);
+        let partly = possible(cached, &[guess(mask![W M U W W])]);
+        assert_eq!(partly, possible(uncached, &[guess(mask![W M U W W])]));
+        assert!(partly.len() > known.len());
+        for (word, _) in known {
+            assert!(partly.iter().any(|&(w, _)| w == word), "{}", word);
+        }
+
+        // The solver still plays on from partial colors.
+        let mut solver = Solver::default();
+        let history = vec![guess(mask![W M U W W])];
+        let next = solver.try_guess(&history).unwrap();
+        assert!(partly.iter().any(|&(w, _)| w == next));
+    }
+
+    #[test]
+    fn reset() {
+        let history = vec![Guess {
+            word: Cow::Borrowed("tares"),
+            mask: mask![W M M W W],
+        }];
+        let mut solver = Solver::default();
+        assert!(solver.ban("drain"));
+        assert_eq!(solver.possible_answers(&history).unwrap().len(), 244);
+
+        solver.reset();
+        assert_eq!(solver.possible_answers(&[]).unwrap().len(), 12947);
+        assert_eq!(solver.try_guess(&history).unwrap(), "drain");
+        let other = vec![Guess {
+            word: Cow::Borrowed("tares"),
+            mask: mask![C C C C W],
+        }];
+        solver.reset();
+        let mut fresh = Solver::default();
+        assert_eq!(
+            solver.possible_answers(&other).unwrap(),
+            fresh.possible_answers(&other).unwrap()
+        );
+    }
+
+    #[test]
+    fn unrelated_histories() {
+        let guess = |mask| Guess {
+            word: Cow::Borrowed("tares"),
+            mask,
+        };
+        let mut solver = Solver::default();
+        assert!(solver.ban("drain"));
+        assert_eq!(
+            solver
+                .possible_answers(&[guess(mask![W M M W W])])
+                .unwrap()
+                .len(),
+            244
+        );
+        // A different history of the same length doesn't build on the one before.
+        let other = [guess(mask![C C C C W])];
+        assert_eq!(
+            solver.possible_answers(&other).unwrap(),
+            Solver::default().possible_answers(&other).unwrap()
+        );
+        // Neither does a shorter one, and bans stay in place either way.
+        assert_eq!(solver.possible_answers(&[]).unwrap().len(), 12946);
+        assert_ne!(
+            solver.try_guess(&[guess(mask![W M M W W])]).unwrap(),
+            "drain"
+        );
+    }
+
+    #[test]
+    fn plays_many_games() {
+        let w = crate::Wordle::new();
+        let mut solver = Solver::default();
+        for answer in ["cigar", "rebut", "sissy"] {
+            assert_eq!(
+                w.play(answer, &mut solver),
+                w.play(answer, Solver::default()),
+                "{}",
+                answer
+            );
+        }
+    }
 }